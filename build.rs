@@ -0,0 +1,82 @@
+/// Generates the registry of solutions that the `runner` binary uses to run all days in-process.
+/// Every `src/bin/YYYY-DD.rs` file is included as a module and its `SOLUTION` constant is collected.
+/// Solutions scaffolded before years were tracked (`src/bin/DD.rs`) are included as well.
+/// Those do not pass a year to `solution!`, which then falls back to `AOC_YEAR` at compile time.
+use std::{env, fs, path::Path};
+
+fn main() {
+    let manifest_dir = env::var("CARGO_MANIFEST_DIR").unwrap();
+    let bin_dir = Path::new(&manifest_dir).join("src").join("bin");
+
+    emit_year(&Path::new(&manifest_dir).join("aoc.toml"));
+
+    println!("cargo:rerun-if-changed={}", bin_dir.display());
+
+    let mut days: Vec<(String, String)> = fs::read_dir(&bin_dir)
+        .map(|entries| {
+            entries
+                .filter_map(Result::ok)
+                .map(|entry| entry.path())
+                .filter_map(|path| {
+                    let stem = path.file_stem()?.to_str()?.to_string();
+                    let is_day = path.extension()? == "rs" && is_solution_name(&stem);
+                    is_day.then(|| (stem.replace('-', "_"), path.display().to_string()))
+                })
+                .collect()
+        })
+        .unwrap_or_default();
+
+    days.sort();
+
+    // NOTE: solutions are left out of test builds, their tests already run as part of each binary.
+    let mut registry = String::new();
+
+    for (day, path) in &days {
+        registry.push_str(&format!(
+            "#[cfg(not(test))]\n#[path = {path:?}]\nmod day_{day};\n"
+        ));
+    }
+
+    registry.push_str("\n/// All solutions that have been scaffolded, sorted by day.\n");
+    registry.push_str("#[cfg(not(test))]\n");
+    registry.push_str("pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[\n");
+    for (day, _) in &days {
+        registry.push_str(&format!("    day_{day}::SOLUTION,\n"));
+    }
+    registry.push_str("];\n\n");
+    registry.push_str("#[cfg(test)]\n");
+    registry
+        .push_str("pub const SOLUTIONS: &[advent_of_code::template::runner::Solution] = &[];\n");
+
+    let out_path = Path::new(&env::var("OUT_DIR").unwrap()).join("solutions.rs");
+    fs::write(out_path, registry).unwrap();
+}
+
+/// Sets `AOC_YEAR` to the `year` configured in `aoc.toml`, unless it is already set in the environment.
+fn emit_year(config_path: &Path) {
+    println!("cargo:rerun-if-env-changed=AOC_YEAR");
+    println!("cargo:rerun-if-changed={}", config_path.display());
+
+    if env::var_os("AOC_YEAR").is_some() {
+        return;
+    }
+
+    let year = fs::read_to_string(config_path)
+        .ok()
+        .and_then(|s| s.parse::<toml::Table>().ok())
+        .and_then(|config| config.get("year")?.as_integer());
+
+    if let Some(year) = year {
+        println!("cargo:rustc-env=AOC_YEAR={year}");
+    }
+}
+
+/// Whether a binary is named after a puzzle, e.g. `2023-01` or `01`.
+fn is_solution_name(stem: &str) -> bool {
+    let is_digits = |s: &str| s.chars().all(|c| c.is_ascii_digit());
+
+    match stem.split_once('-') {
+        Some((year, day)) => year.len() == 4 && is_digits(year) && day.len() == 2 && is_digits(day),
+        None => stem.len() == 2 && is_digits(stem),
+    }
+}
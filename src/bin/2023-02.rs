@@ -1,6 +1,4 @@
-advent_of_code::solution!(2);
-
-use std::u32;
+advent_of_code::solution!(year: 2023, 2, parse: parse_games);
 
 use anyhow::{Ok, Result};
 use pest::Parser;
@@ -11,7 +9,7 @@ use pest_derive::Parser;
 struct SubSet(u32, u32, u32);
 
 #[derive(Default, Debug, PartialEq, Eq)]
-struct Game {
+pub struct Game {
     id: u32,
     subsets: Vec<SubSet>,
 }
@@ -81,16 +79,16 @@ fn parse_game(input: &str) -> Result<Game> {
     Ok(game)
 }
 
-fn parse_games(input: &str) -> Result<Vec<Game>> {
-    let mut games = Vec::new();
-    for line in input.lines() {
-        games.push(parse_game(line)?);
-    }
-    Ok(games)
+type Games = Vec<Game>;
+
+fn parse_games(input: &str) -> Games {
+    input
+        .lines()
+        .map(|line| parse_game(line).expect("Unable to parse game"))
+        .collect()
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let games = parse_games(input).unwrap();
+pub fn part_one(games: &Games) -> Option<u32> {
     let max_red = 12;
     let max_green = 13;
     let max_blue = 14;
@@ -111,8 +109,7 @@ pub fn part_one(input: &str) -> Option<u32> {
     Some(total)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let games = parse_games(input).unwrap();
+pub fn part_two(games: &Games) -> Option<u32> {
     let total = games.iter().fold(0, |mut sum, game| {
         let mut max_red = 0;
         let mut max_green = 0;
@@ -155,13 +152,15 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_one(&parse_games(&input));
         assert_eq!(result, Some(8));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_two(&parse_games(&input));
         assert_eq!(result, Some(2286));
     }
 
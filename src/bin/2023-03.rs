@@ -0,0 +1,133 @@
+advent_of_code::solution!(year: 2023, 3);
+
+use advent_of_code::grid::Grid;
+use itertools::Itertools;
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Cell {
+    Empty,
+    Symbol(char),
+    /// A digit of the number at this index in [`Schematic::numbers`].
+    Number(usize),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+struct Schematic {
+    grid: Grid<Cell>,
+    numbers: Vec<usize>,
+}
+
+impl Schematic {
+    /// Returns every symbol with the indices of the distinct numbers next to it, diagonals
+    /// included.
+    fn symbol_neighbors(&self) -> impl Iterator<Item = (char, Vec<usize>)> + '_ {
+        self.grid
+            .enumerate()
+            .filter_map(move |(position, &cell)| match cell {
+                Cell::Symbol(symbol) => {
+                    let numbers = self
+                        .grid
+                        .adjacent(position)
+                        .filter_map(|neighbor| match self.grid[neighbor] {
+                            Cell::Number(index) => Some(index),
+                            _ => None,
+                        })
+                        .unique()
+                        .collect();
+                    Some((symbol, numbers))
+                }
+                _ => None,
+            })
+    }
+}
+
+fn parse_schematic(input: &str) -> Schematic {
+    let chars: Grid<char> = input.parse().expect("Unable to parse schematic");
+    let mut numbers: Vec<usize> = Vec::new();
+    let mut cells = Vec::with_capacity(chars.width() * chars.height());
+
+    for row in chars.rows() {
+        let mut in_number = false;
+        for &c in row {
+            let cell = match c.to_digit(10) {
+                Some(digit) => {
+                    if !in_number {
+                        numbers.push(0);
+                    }
+                    let number = numbers.last_mut().expect("A number was just started");
+                    *number = *number * 10 + digit as usize;
+                    Cell::Number(numbers.len() - 1)
+                }
+                None if c == '.' => Cell::Empty,
+                None => Cell::Symbol(c),
+            };
+            in_number = matches!(cell, Cell::Number(_));
+            cells.push(cell);
+        }
+    }
+
+    Schematic {
+        grid: Grid::new(chars.width(), chars.height(), cells),
+        numbers,
+    }
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let schematic = parse_schematic(input);
+    let total = schematic
+        .symbol_neighbors()
+        .flat_map(|(_, numbers)| numbers)
+        .unique()
+        .map(|index| schematic.numbers[index])
+        .sum();
+    Some(total)
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    let schematic = parse_schematic(input);
+    let total = schematic
+        .symbol_neighbors()
+        .filter(|(symbol, numbers)| *symbol == '*' && numbers.len() == 2)
+        .map(|(_, numbers)| {
+            numbers
+                .iter()
+                .map(|&index| schematic.numbers[index])
+                .product::<usize>()
+        })
+        .sum();
+    Some(total)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(4361));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(467835));
+    }
+
+    #[test]
+    fn test_parse_schematic() {
+        let line = "467..114..\n*........*";
+        let (a, b) = (Cell::Number(0), Cell::Number(1));
+        let (empty, star) = (Cell::Empty, Cell::Symbol('*'));
+        #[rustfmt::skip]
+        let cells = vec![
+            a, a, a, empty, empty, b, b, b, empty, empty,
+            star, empty, empty, empty, empty, empty, empty, empty, empty, star,
+        ];
+        let schematic = Schematic {
+            grid: Grid::new(10, 2, cells),
+            numbers: vec![467, 114],
+        };
+        assert_eq!(parse_schematic(line), schematic);
+    }
+}
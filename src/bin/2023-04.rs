@@ -1,4 +1,4 @@
-advent_of_code::solution!(4);
+advent_of_code::solution!(year: 2023, 4, parse: get_card_point_map);
 
 use pest::Parser;
 use pest_derive::Parser;
@@ -7,7 +7,10 @@ use pest_derive::Parser;
 #[grammar = "parsers/day4.pest"]
 struct CardsParser;
 
-fn get_card_point_map(input: &str) -> Vec<u32> {
+/// The number of matching numbers for each card.
+type CardPoints = Vec<u32>;
+
+fn get_card_point_map(input: &str) -> CardPoints {
     let cards_parse = CardsParser::parse(Rule::cards, input)
         .unwrap()
         .next()
@@ -69,16 +72,15 @@ fn get_card_point_map(input: &str) -> Vec<u32> {
     result
 }
 
-pub fn part_one(input: &str) -> Option<u32> {
-    let points = get_card_point_map(input)
+pub fn part_one(points: &CardPoints) -> Option<u32> {
+    let points = points
         .iter()
         .map(|num| if num > &0 { 2_u32.pow(num - 1) } else { 0 })
         .sum();
     Some(points)
 }
 
-pub fn part_two(input: &str) -> Option<u32> {
-    let points = get_card_point_map(input);
+pub fn part_two(points: &CardPoints) -> Option<u32> {
     let len = points.len();
     let mut cards = vec![1; len];
     for (idx, point) in points.iter().enumerate() {
@@ -100,19 +102,21 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_one(&get_card_point_map(&input));
         assert_eq!(result, Some(13));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_two(&get_card_point_map(&input));
         assert_eq!(result, Some(30));
     }
 
     #[test]
     fn test_parse_cards() {
-        let points = get_card_point_map(&advent_of_code::template::read_file("examples", DAY));
+        let points = get_card_point_map(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(points, vec![4, 2, 2, 1, 0, 0]);
     }
 }
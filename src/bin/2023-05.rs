@@ -0,0 +1,81 @@
+use advent_of_code::interval::{RangeMap, RangeSet};
+advent_of_code::solution!(year: 2023, 5, parse: parse_input);
+
+/// The almanac maps composed into a single seed-to-location map, and the list of seeds.
+type Almanac = (RangeMap<u64>, Vec<u64>);
+
+// Parse each section of the input into a map and chain them into a single map
+fn parse_input(input: &str) -> Almanac {
+    let mut sections_str = input.split("\n\n");
+    let seeds = sections_str.next().expect("Unable to parse seeds");
+
+    let seeds: Vec<u64> = seeds
+        .split_whitespace()
+        .skip(1)
+        .map(|num| num.parse::<u64>().unwrap())
+        .collect();
+
+    let map = sections_str
+        .map(|src_map| {
+            src_map
+                .lines()
+                .skip(1) // skip title line
+                .map(|line| {
+                    let mut nums = line.split_whitespace();
+                    let dest_start = nums
+                        .next()
+                        .expect("Unable to retrieve destination map start value")
+                        .parse::<u64>()
+                        .expect("Unable to parse destination map start value");
+                    let src_start = nums
+                        .next()
+                        .expect("Unable to retrieve source map start value")
+                        .parse::<u64>()
+                        .expect("Unable to parse source map start value");
+                    let len = nums
+                        .next()
+                        .expect("Unable to retrieve source map length")
+                        .parse::<u64>()
+                        .expect("Unable to parse source map length");
+                    (src_start..src_start + len, dest_start)
+                })
+                .collect::<RangeMap<u64>>()
+        })
+        .fold(RangeMap::new(), |map, section| map.then(&section));
+
+    (map, seeds)
+}
+
+pub fn part_one(almanac: &Almanac) -> Option<u64> {
+    let (map, seeds) = almanac;
+    seeds.iter().map(|&seed| map.get(seed)).min()
+}
+
+pub fn part_two(almanac: &Almanac) -> Option<u64> {
+    let (map, seeds) = almanac;
+    let seed_ranges: RangeSet<u64> = seeds
+        .chunks_exact(2)
+        .map(|chunk| chunk[0]..chunk[0] + chunk[1])
+        .collect();
+
+    map.map_set(&seed_ranges).min()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_one(&parse_input(&input));
+        assert_eq!(result, Some(35));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_two(&parse_input(&input));
+        assert_eq!(result, Some(46));
+    }
+}
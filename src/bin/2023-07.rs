@@ -1,6 +1,6 @@
 use std::{char, cmp::Ordering};
 
-advent_of_code::solution!(7);
+advent_of_code::solution!(year: 2023, 7);
 
 #[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
 enum HandType {
@@ -243,13 +243,13 @@ mod tests {
 
     #[test]
     fn test_part_one() {
-        let result = part_one(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(6440));
     }
 
     #[test]
     fn test_part_two() {
-        let result = part_two(&advent_of_code::template::read_file("examples", DAY));
+        let result = part_two(&advent_of_code::template::read_file("examples", PUZZLE));
         assert_eq!(result, Some(5905));
     }
 }
@@ -0,0 +1,99 @@
+use advent_of_code::cycle::{first_common_hit, hashed};
+use regex::Regex;
+use std::collections::HashMap;
+
+advent_of_code::solution!(year: 2023, 8, parse: parse_input);
+
+/// The instructions and the network of nodes.
+type Network<'a> = (Vec<u8>, HashMap<&'a str, (&'a str, &'a str)>);
+
+/// Return a a tuple containing:
+///   - The instructions as a vector of 0s and 1s
+///   - A HashMap of the network of nodes
+fn parse_input(input: &str) -> Network<'_> {
+    let mut input = input.split("\n\n");
+    let instructions = input.next().unwrap();
+    let instructions = instructions
+        .chars()
+        .map(|c| match c {
+            'L' => 0,
+            'R' => 1,
+            _ => unreachable!(),
+        })
+        .collect();
+
+    let mut network = HashMap::new();
+    let nodes = input.next().unwrap();
+    let pattern = r"(?m)^(\w+)\s*=\s*\((\w+),\s*(\w+)\)$";
+    let re = Regex::new(pattern).unwrap();
+    for (_, [src, left, right]) in re.captures_iter(nodes).map(|x| x.extract()) {
+        network.insert(src, (left, right));
+    }
+
+    (instructions, network)
+}
+
+pub fn part_one(input: &Network) -> Option<usize> {
+    let (instructions, network) = input;
+    let mut steps = 0;
+    let goal = "ZZZ";
+    let instruction_count = instructions.len();
+    let mut current_node = String::from("AAA");
+    while current_node != goal {
+        let instruction = instructions[steps % instruction_count];
+        steps += 1;
+        let node = match instruction {
+            0 => network.get(current_node.as_str()).unwrap().0,
+            1 => network.get(current_node.as_str()).unwrap().1,
+            _ => unreachable!(),
+        };
+        current_node.clear();
+        current_node.push_str(node);
+    }
+    Some(steps)
+}
+
+pub fn part_two(input: &Network) -> Option<usize> {
+    let (instructions, network) = input;
+    let instruction_count = instructions.len();
+
+    // A ghost is in the same state if it is at the same node and about to follow the same
+    // instruction, so its path is an orbit that eventually repeats.
+    let step = |&(node, index): &(&str, usize)| {
+        let next = match instructions[index] {
+            0 => network.get(node).unwrap().0,
+            1 => network.get(node).unwrap().1,
+            _ => unreachable!(),
+        };
+        (next, (index + 1) % instruction_count)
+    };
+
+    let orbits = network
+        .keys()
+        .filter(|key| key.ends_with('A'))
+        .map(|&key| hashed((key, 0), step, |(node, _)| node.ends_with('Z')))
+        .collect::<Vec<_>>();
+
+    // The ghosts may reach a Z more than once per cycle and at any offset, so combine the
+    // orbits instead of taking the lcm of the cycle lengths.
+    first_common_hit(&orbits)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        let result = part_one(&parse_input(&input));
+        assert_eq!(result, Some(6));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::template::read_file_part("examples", PUZZLE, 2);
+        let result = part_two(&parse_input(&input));
+        assert_eq!(result, Some(6));
+    }
+}
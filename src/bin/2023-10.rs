@@ -0,0 +1,168 @@
+use advent_of_code::geometry::{interior_points, Direction, Point};
+use advent_of_code::grid::{Grid, Position};
+use anyhow::{anyhow, Ok, Result};
+
+advent_of_code::solution!(year: 2023, 10);
+
+#[derive(PartialEq, Eq)]
+enum Tile {
+    Vert,
+    Hor,
+    NE,
+    NW,
+    SW,
+    SE,
+    None,
+    Start,
+}
+
+impl TryFrom<char> for Tile {
+    type Error = anyhow::Error;
+
+    fn try_from(value: char) -> Result<Self, Self::Error> {
+        match value {
+            '|' => Ok(Tile::Vert),
+            '-' => Ok(Tile::Hor),
+            'L' => Ok(Tile::NE),
+            'J' => Ok(Tile::NW),
+            '7' => Ok(Tile::SW),
+            'F' => Ok(Tile::SE),
+            '.' => Ok(Tile::None),
+            'S' => Ok(Tile::Start),
+            _ => Err(anyhow!("Unable to parse value: {}", value)),
+        }
+    }
+}
+
+impl Tile {
+    /// The direction of the loop after entering this tile in `direction`.
+    fn turn(&self, direction: Direction) -> Option<Direction> {
+        match (self, direction) {
+            (Tile::Vert, Direction::North | Direction::South)
+            | (Tile::Hor, Direction::West | Direction::East) => Some(direction),
+            (Tile::NE, Direction::West) | (Tile::NW, Direction::East) => Some(Direction::North),
+            (Tile::SE, Direction::West) | (Tile::SW, Direction::East) => Some(Direction::South),
+            (Tile::NW, Direction::South) | (Tile::SW, Direction::North) => Some(Direction::West),
+            (Tile::NE, Direction::South) | (Tile::SE, Direction::North) => Some(Direction::East),
+            _ => None,
+        }
+    }
+
+    /// Whether the loop can enter this tile moving in `direction`.
+    fn accepts(&self, direction: Direction) -> bool {
+        *self == Tile::Start || self.turn(direction).is_some()
+    }
+}
+
+struct Node {
+    tile: Tile,
+    next: Option<Position>,
+    prev: Option<Position>,
+}
+
+impl Node {
+    fn new(tile: Tile) -> Self {
+        Self {
+            tile,
+            next: None,
+            prev: None,
+        }
+    }
+}
+
+fn parse_input(input: &str) -> Grid<Node> {
+    Grid::try_parse(input, |c| Tile::try_from(c).map(Node::new)).expect("Unable to parse input")
+}
+
+fn get_next_loop_neighbor(
+    input: &Grid<Node>,
+    position: Position,
+    direction: Option<Direction>,
+) -> Option<(Position, Direction)> {
+    if let Some(direction) = direction {
+        // The next node is based on the value of the current node and the direction of the loop
+        let direction = input[position].tile.turn(direction)?;
+        input
+            .step(position, direction.offset())
+            .map(|next| (next, direction))
+    } else {
+        // If no direction is provided, check all neighbors clockwise and return the first one
+        Direction::CARDINAL.into_iter().find_map(|direction| {
+            input
+                .step(position, direction.offset())
+                .filter(|&next| input[next].tile.accepts(direction))
+                .map(|next| (next, direction))
+        })
+    }
+}
+
+fn get_starting_index(input: &Grid<Node>) -> Option<Position> {
+    input.position(|node| node.tile == Tile::Start)
+}
+
+fn mark_loop(input: &mut Grid<Node>) -> usize {
+    let start_idx = get_starting_index(input).expect("Unable to find starting tile");
+    let mut last_idx = start_idx;
+    let mut dir = None;
+    let mut steps = 0;
+    while let Some((node_idx, direction)) = get_next_loop_neighbor(input, last_idx, dir) {
+        steps += 1;
+        // Set the previous node
+        input[node_idx].prev = Some(last_idx);
+        // Set the next node
+        input[last_idx].next = Some(node_idx);
+        dir = Some(direction);
+        if node_idx == start_idx {
+            break;
+        }
+        last_idx = node_idx;
+    }
+    steps
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    let mut input = parse_input(input);
+    let points = mark_loop(&mut input);
+    Some(points / 2)
+}
+
+// To find the number of points that exist in a polygon, we can use Pick's Theorem to relate the
+// area of a polygon to the number of interior points and vertices. To find the area of a polygon,
+// use the shoelace formula.
+pub fn part_two(input: &str) -> Option<usize> {
+    let mut input = parse_input(input);
+    let points = mark_loop(&mut input);
+    let start_idx = get_starting_index(&input).expect("Unable to find starting tile");
+
+    let mut current_node = start_idx;
+    let mut vertices = Vec::with_capacity(points);
+    for _ in 0..points {
+        vertices.push(Point::from_position(current_node).into());
+        current_node = input[current_node].next.unwrap();
+    }
+
+    Some(interior_points(&vertices) as usize)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(4));
+        let result = part_one(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 2,
+        ));
+        assert_eq!(result, Some(8));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let result = part_two(&advent_of_code::template::read_file_part(
+            "examples", PUZZLE, 3,
+        ));
+        assert_eq!(result, Some(4));
+    }
+}
@@ -0,0 +1,118 @@
+advent_of_code::solution!(year: 2023, 11);
+
+use advent_of_code::geometry::Point;
+use advent_of_code::grid::Grid;
+
+#[derive(PartialEq, Eq, Clone, Copy, Debug)]
+enum Pixel {
+    Empty,
+    Galaxy,
+}
+
+type Image = Grid<Pixel>;
+type Galaxies = Vec<Point<usize>>;
+
+fn parse_input(input: &str) -> Image {
+    Grid::parse(input, |c| match c {
+        '.' => Pixel::Empty,
+        '#' => Pixel::Galaxy,
+        _ => unreachable!(),
+    })
+    .expect("Unable to parse image")
+}
+
+fn get_empty_rows(image: &Image) -> Vec<usize> {
+    image
+        .rows()
+        .enumerate()
+        .filter_map(|(index, row)| (!row.contains(&Pixel::Galaxy)).then_some(index))
+        .collect()
+}
+
+fn get_empty_columns(image: &Image) -> Vec<usize> {
+    image
+        .columns()
+        .enumerate()
+        .filter_map(|(index, mut column)| {
+            (!column.any(|&pixel| pixel == Pixel::Galaxy)).then_some(index)
+        })
+        .collect()
+}
+
+fn get_galaxies(image: &Image) -> Galaxies {
+    image
+        .enumerate()
+        .filter(|(_, &pixel)| pixel == Pixel::Galaxy)
+        .map(|(position, _)| Point::from_position(position))
+        .collect()
+}
+
+fn expand_galaxies(
+    galaxies: Galaxies,
+    empty_rows: &[usize],
+    empty_columms: &[usize],
+    expansion_amount: usize,
+) -> Galaxies {
+    let mut new_galaxies = Vec::new();
+    for Point { x, y } in galaxies {
+        let empty_rows_before = empty_rows.iter().filter(|&&idx| idx < y).count();
+        let empty_columns_before = empty_columms.iter().filter(|&&idx| idx < x).count();
+        let new_x = x + (empty_columns_before * expansion_amount);
+        let new_y = y + (empty_rows_before * expansion_amount);
+        new_galaxies.push(Point::new(new_x, new_y));
+    }
+    new_galaxies
+}
+
+fn get_galaxy_distances(input: &str, expansion_amount: usize) -> usize {
+    let image = parse_input(input);
+    let galaxies = get_galaxies(&image);
+    let empty_rows = get_empty_rows(&image);
+    let empty_columms = get_empty_columns(&image);
+    let galaxies = expand_galaxies(galaxies, &empty_rows, &empty_columms, expansion_amount);
+    let galaxy_count = galaxies.len();
+    let mut sum = 0;
+    for (index, galaxy) in galaxies.iter().enumerate() {
+        if index < galaxy_count - 1 {
+            for next in &galaxies[(index + 1)..galaxy_count] {
+                sum += galaxy.manhattan_distance(*next);
+            }
+        }
+    }
+    sum
+}
+
+pub fn part_one(input: &str) -> Option<usize> {
+    Some(get_galaxy_distances(input, 1))
+}
+
+pub fn part_two(input: &str) -> Option<usize> {
+    Some(get_galaxy_distances(input, 1000000 - 1))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_part_one() {
+        let result = part_one(&advent_of_code::template::read_file("examples", PUZZLE));
+        assert_eq!(result, Some(374));
+    }
+
+    #[test]
+    fn test_part_two() {
+        let input = advent_of_code::template::read_file("examples", PUZZLE);
+        assert_eq!(get_galaxy_distances(&input, 10 - 1), 1030);
+        assert_eq!(get_galaxy_distances(&input, 100 - 1), 8410);
+    }
+
+    #[test]
+    fn test_empty_rows_and_columns() {
+        let img = parse_input(&advent_of_code::template::read_file("examples", PUZZLE));
+        let empty_columms = get_empty_columns(&img);
+        assert_eq!(empty_columms, vec![2, 5, 8]);
+        let empty_rows = get_empty_rows(&img);
+        assert_eq!(empty_rows, vec![3, 7]);
+    }
+}
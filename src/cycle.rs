@@ -0,0 +1,312 @@
+/// Cycle detection for sequences of states `start, step(start), step(step(start)), …`, e.g. a
+/// ghost walking a network or a platform that is tilted over and over.
+///
+/// Every detector returns the same [`Orbit`], they only differ in what they require of a state:
+/// [`floyd`] and [`brent`] compare states and keep two of them in memory, [`hashed`] remembers
+/// every state but walks the sequence only once.
+use std::{collections::HashMap, hash::Hash};
+
+use itertools::Itertools;
+
+use crate::math::crt;
+
+/// The shape of a sequence of states: `offset` steps lead into a cycle of `length` steps.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Orbit {
+    pub offset: usize,
+    pub length: usize,
+    /// Steps at which the sequence reaches a goal, in ascending order and before
+    /// `offset + length`. Hits from `offset` on repeat every `length` steps.
+    pub hits: Vec<usize>,
+}
+
+impl Orbit {
+    /// Returns `true` if the sequence reaches a goal after `step` steps.
+    pub fn is_hit(&self, step: usize) -> bool {
+        let step = if step < self.offset {
+            step
+        } else {
+            self.offset + (step - self.offset) % self.length
+        };
+        self.hits.binary_search(&step).is_ok()
+    }
+
+    /// The hits that repeat every `length` steps.
+    pub fn cycle_hits(&self) -> &[usize] {
+        &self.hits[self.hits.partition_point(|&hit| hit < self.offset)..]
+    }
+
+    /// Collects the hits of a sequence once its offset and length are known.
+    fn walk<T>(
+        start: T,
+        offset: usize,
+        length: usize,
+        mut step: impl FnMut(&T) -> T,
+        mut is_goal: impl FnMut(&T) -> bool,
+    ) -> Self {
+        let mut hits = Vec::new();
+        let mut state = start;
+
+        for index in 0..offset + length {
+            if is_goal(&state) {
+                hits.push(index);
+            }
+            state = step(&state);
+        }
+
+        Self {
+            offset,
+            length,
+            hits,
+        }
+    }
+}
+
+/// Detects the cycle with Floyd's tortoise and hare.
+/// See <https://en.wikipedia.org/wiki/Cycle_detection#Floyd's_tortoise_and_hare>.
+pub fn floyd<T: Clone + PartialEq>(
+    start: T,
+    mut step: impl FnMut(&T) -> T,
+    is_goal: impl FnMut(&T) -> bool,
+) -> Orbit {
+    let mut tortoise = step(&start);
+    let mut hare = step(&tortoise);
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        let ahead = step(&hare);
+        hare = step(&ahead);
+    }
+
+    let mut offset = 0;
+    tortoise = start.clone();
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+
+    let mut length = 1;
+    hare = step(&tortoise);
+    while tortoise != hare {
+        hare = step(&hare);
+        length += 1;
+    }
+
+    Orbit::walk(start, offset, length, step, is_goal)
+}
+
+/// Detects the cycle with Brent's algorithm, which needs fewer steps than [`floyd`].
+/// See <https://en.wikipedia.org/wiki/Cycle_detection#Brent's_algorithm>.
+pub fn brent<T: Clone + PartialEq>(
+    start: T,
+    mut step: impl FnMut(&T) -> T,
+    is_goal: impl FnMut(&T) -> bool,
+) -> Orbit {
+    let mut power = 1;
+    let mut length = 1;
+    let mut tortoise = start.clone();
+    let mut hare = step(&start);
+    while tortoise != hare {
+        if power == length {
+            tortoise = hare.clone();
+            power *= 2;
+            length = 0;
+        }
+        hare = step(&hare);
+        length += 1;
+    }
+
+    let mut offset = 0;
+    tortoise = start.clone();
+    hare = start.clone();
+    for _ in 0..length {
+        hare = step(&hare);
+    }
+    while tortoise != hare {
+        tortoise = step(&tortoise);
+        hare = step(&hare);
+        offset += 1;
+    }
+
+    Orbit::walk(start, offset, length, step, is_goal)
+}
+
+/// Detects the cycle by remembering the step at which every state was first seen.
+pub fn hashed<T: Hash + Eq>(
+    start: T,
+    mut step: impl FnMut(&T) -> T,
+    mut is_goal: impl FnMut(&T) -> bool,
+) -> Orbit {
+    let mut seen = HashMap::new();
+    let mut hits = Vec::new();
+    let mut state = start;
+
+    for index in 0.. {
+        if let Some(&offset) = seen.get(&state) {
+            return Orbit {
+                offset,
+                length: index - offset,
+                hits,
+            };
+        }
+        if is_goal(&state) {
+            hits.push(index);
+        }
+        let next = step(&state);
+        seen.insert(state, index);
+        state = next;
+    }
+
+    unreachable!("the sequence has more states than fit in memory")
+}
+
+/// Returns the first step at which every sequence reaches a goal at the same time, or [`None`]
+/// if that never happens, does not fit in an `i64` or there are no sequences.
+///
+/// Until every sequence is in its cycle the steps are checked one by one, after that the hits
+/// in the cycles are combined with the Chinese remainder theorem. The cycles need not be aligned
+/// with the hits, nor their lengths be coprime.
+pub fn first_common_hit(orbits: &[Orbit]) -> Option<usize> {
+    let (first, rest) = orbits.split_first()?;
+    let settled = orbits.iter().map(|orbit| orbit.offset).max()?;
+
+    let early = (0..settled)
+        .filter(|&step| first.is_hit(step))
+        .find(|&step| rest.iter().all(|orbit| orbit.is_hit(step)));
+    if early.is_some() {
+        return early;
+    }
+
+    // NOTE: merge one orbit at a time, so that only the hits compatible with every orbit so far
+    // are kept instead of every combination of hits.
+    let mut solutions = vec![(0, 1)];
+    for orbit in orbits {
+        let length = i64::try_from(orbit.length).ok()?;
+        solutions = solutions
+            .iter()
+            .cartesian_product(orbit.cycle_hits())
+            .filter_map(|(&solution, &hit)| crt([solution, (i64::try_from(hit).ok()?, length)]))
+            .collect();
+        solutions.sort_unstable();
+        solutions.dedup();
+    }
+
+    solutions
+        .into_iter()
+        .filter_map(|(step, modulus)| {
+            let (step, modulus) = (usize::try_from(step).ok()?, usize::try_from(modulus).ok()?);
+            // NOTE: the solution may lie before the offset of a sequence, move it into the cycles.
+            Some(step + settled.saturating_sub(step).div_ceil(modulus) * modulus)
+        })
+        .min()
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use proptest::{collection::vec, prelude::*};
+
+    use super::{brent, first_common_hit, floyd, hashed, Orbit};
+
+    /// A functional graph of states `0..n`, every state has one successor.
+    fn graph() -> impl Strategy<Value = (Vec<usize>, Vec<bool>)> {
+        (1..16usize).prop_flat_map(|n| (vec(0..n, n), vec(any::<bool>(), n)))
+    }
+
+    #[test]
+    fn detects_cycles() {
+        // 0 → 1 → 2 → 3 → 4 → 2
+        let next = [1, 2, 3, 4, 2];
+        let expected = Orbit {
+            offset: 2,
+            length: 3,
+            hits: vec![1, 3],
+        };
+
+        let step = |&state: &usize| next[state];
+        let is_goal = |&state: &usize| state % 2 == 1;
+        assert_eq!(floyd(0, step, is_goal), expected);
+        assert_eq!(brent(0, step, is_goal), expected);
+        assert_eq!(hashed(0, step, is_goal), expected);
+
+        assert_eq!(expected.cycle_hits(), [3]);
+        assert!(expected.is_hit(1));
+        assert!(!expected.is_hit(4));
+        assert!(expected.is_hit(6));
+        assert!(expected.is_hit(3000));
+    }
+
+    #[test]
+    fn combines_cycles() {
+        // the ghosts of 2023 day 8 reach their goal once per cycle, but at different offsets.
+        let a = Orbit {
+            offset: 1,
+            length: 2,
+            hits: vec![2],
+        };
+        let b = Orbit {
+            offset: 1,
+            length: 3,
+            hits: vec![3],
+        };
+        assert_eq!(first_common_hit(&[a.clone(), b]), Some(6));
+
+        // a hit before either sequence is in its cycle.
+        let c = Orbit {
+            offset: 4,
+            length: 4,
+            hits: vec![0, 2, 5],
+        };
+        assert_eq!(first_common_hit(&[a.clone(), c]), Some(2));
+
+        let odd = Orbit {
+            offset: 0,
+            length: 2,
+            hits: vec![1],
+        };
+        assert_eq!(first_common_hit(&[a, odd]), None);
+        assert_eq!(first_common_hit(&[]), None);
+    }
+
+    proptest! {
+        #[test]
+        fn detectors_match_brute_force((next, goals) in graph(), start in 0..16usize) {
+            let start = start % next.len();
+            let step = |&state: &usize| next[state];
+            let is_goal = |&state: &usize| goals[state];
+
+            let mut states = vec![start];
+            while !states[..states.len() - 1].contains(states.last().unwrap()) {
+                states.push(next[*states.last().unwrap()]);
+            }
+            let last = *states.last().unwrap();
+            let offset = states.iter().position(|&state| state == last).unwrap();
+            let expected = Orbit {
+                offset,
+                length: states.len() - 1 - offset,
+                hits: (0..states.len() - 1).filter(|&i| goals[states[i]]).collect(),
+            };
+
+            prop_assert_eq!(floyd(start, step, is_goal), expected.clone());
+            prop_assert_eq!(brent(start, step, is_goal), expected.clone());
+            prop_assert_eq!(hashed(start, step, is_goal), expected);
+        }
+
+        #[test]
+        fn first_common_hit_matches_brute_force(
+            (next, goals) in graph(),
+            starts in vec(0..16usize, 1..4),
+        ) {
+            let orbits: Vec<Orbit> = starts
+                .iter()
+                .map(|start| hashed(start % next.len(), |&s| next[s], |&s| goals[s]))
+                .collect();
+
+            // the hits repeat once every orbit is in its cycle, after the lcm of their lengths.
+            let expected = (0..16 + 15 * 14 * 13)
+                .find(|&step| orbits.iter().all(|orbit| orbit.is_hit(step)));
+            prop_assert_eq!(first_common_hit(&orbits), expected);
+        }
+    }
+}
@@ -0,0 +1,282 @@
+/// Points, directions and polygons on an integer plane.
+///
+/// The y axis points down, like the rows of a [`Grid`](crate::grid::Grid), so that
+/// [`Direction::North`] moves to the previous row.
+use std::ops::{Add, AddAssign, Mul, Neg, Sub};
+
+use crate::grid::Position;
+use crate::math::gcd;
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Point<T> {
+    pub x: T,
+    pub y: T,
+}
+
+impl<T> Point<T> {
+    pub const fn new(x: T, y: T) -> Self {
+        Self { x, y }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    /// The distance when moving along the axes, i.e. `|dx| + |dy|`.
+    pub fn manhattan_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x) + abs_diff(self.y, other.y)
+    }
+
+    /// The distance when moving diagonally is allowed, i.e. `max(|dx|, |dy|)`.
+    pub fn chebyshev_distance(self, other: Self) -> T {
+        abs_diff(self.x, other.x).max(abs_diff(self.y, other.y))
+    }
+}
+
+impl Point<usize> {
+    /// Converts the `(row, column)` position of a grid cell.
+    pub fn from_position((row, column): Position) -> Self {
+        Self::new(column, row)
+    }
+
+    /// The `(row, column)` position of the grid cell at this point.
+    pub fn position(self) -> Position {
+        (self.y, self.x)
+    }
+
+    /// Moves one step in a direction, returns [`None`] if that leaves the first quadrant.
+    pub fn step(self, direction: Direction) -> Option<Self> {
+        let delta = direction.delta();
+        Some(Self::new(
+            self.x.checked_add_signed(delta.x)?,
+            self.y.checked_add_signed(delta.y)?,
+        ))
+    }
+}
+
+impl From<Point<usize>> for Point<i64> {
+    fn from(point: Point<usize>) -> Self {
+        Self::new(point.x as i64, point.y as i64)
+    }
+}
+
+impl<T: Add<Output = T>> Add for Point<T> {
+    type Output = Self;
+
+    fn add(self, rhs: Self) -> Self::Output {
+        Self::new(self.x + rhs.x, self.y + rhs.y)
+    }
+}
+
+impl<T: AddAssign> AddAssign for Point<T> {
+    fn add_assign(&mut self, rhs: Self) {
+        self.x += rhs.x;
+        self.y += rhs.y;
+    }
+}
+
+impl<T: Sub<Output = T>> Sub for Point<T> {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self::new(self.x - rhs.x, self.y - rhs.y)
+    }
+}
+
+impl<T: Mul<Output = T> + Copy> Mul<T> for Point<T> {
+    type Output = Self;
+
+    fn mul(self, rhs: T) -> Self::Output {
+        Self::new(self.x * rhs, self.y * rhs)
+    }
+}
+
+impl<T: Neg<Output = T>> Neg for Point<T> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::new(-self.x, -self.y)
+    }
+}
+
+fn abs_diff<T: Ord + Sub<Output = T>>(a: T, b: T) -> T {
+    if a > b {
+        a - b
+    } else {
+        b - a
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// One of the eight compass directions, in clockwise order starting at north.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Direction {
+    North,
+    NorthEast,
+    East,
+    SouthEast,
+    South,
+    SouthWest,
+    West,
+    NorthWest,
+}
+
+impl Direction {
+    /// The four directions along the axes, clockwise starting at north.
+    pub const CARDINAL: [Direction; 4] = [
+        Direction::North,
+        Direction::East,
+        Direction::South,
+        Direction::West,
+    ];
+
+    /// All eight directions, clockwise starting at north.
+    pub const ALL: [Direction; 8] = [
+        Direction::North,
+        Direction::NorthEast,
+        Direction::East,
+        Direction::SouthEast,
+        Direction::South,
+        Direction::SouthWest,
+        Direction::West,
+        Direction::NorthWest,
+    ];
+
+    /// The change of a point after one step in this direction.
+    pub fn delta(self) -> Point<isize> {
+        let (x, y) = match self {
+            Direction::North => (0, -1),
+            Direction::NorthEast => (1, -1),
+            Direction::East => (1, 0),
+            Direction::SouthEast => (1, 1),
+            Direction::South => (0, 1),
+            Direction::SouthWest => (-1, 1),
+            Direction::West => (-1, 0),
+            Direction::NorthWest => (-1, -1),
+        };
+        Point::new(x, y)
+    }
+
+    /// The change of a `(row, column)` position, e.g. for [`Grid::step`](crate::grid::Grid::step).
+    pub fn offset(self) -> (isize, isize) {
+        let delta = self.delta();
+        (delta.y, delta.x)
+    }
+
+    /// Turns 90 degrees counterclockwise.
+    pub fn turn_left(self) -> Self {
+        self.rotate(6)
+    }
+
+    /// Turns 90 degrees clockwise.
+    pub fn turn_right(self) -> Self {
+        self.rotate(2)
+    }
+
+    /// Turns 180 degrees.
+    pub fn reverse(self) -> Self {
+        self.rotate(4)
+    }
+
+    pub fn is_diagonal(self) -> bool {
+        !Self::CARDINAL.contains(&self)
+    }
+
+    /// Rotates clockwise in steps of 45 degrees.
+    fn rotate(self, steps: usize) -> Self {
+        Self::ALL[(self as usize + steps) % Self::ALL.len()]
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Returns twice the area of a simple polygon with the shoelace formula. Doubling keeps the
+/// result an integer, the area of a polygon with integer vertices is a multiple of one half.
+///
+/// The vertices may be listed in either orientation, the polygon is closed implicitly.
+/// See <https://en.wikipedia.org/wiki/Shoelace_formula>.
+pub fn double_area(vertices: &[Point<i64>]) -> i64 {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+    edges.map(|(a, b)| a.x * b.y - a.y * b.x).sum::<i64>().abs()
+}
+
+/// Returns the number of integer points on the edges of a polygon, including its vertices.
+pub fn boundary_points(vertices: &[Point<i64>]) -> i64 {
+    let edges = vertices.iter().zip(vertices.iter().cycle().skip(1));
+    edges
+        .map(|(a, b)| gcd((b.x - a.x).unsigned_abs(), (b.y - a.y).unsigned_abs()) as i64)
+        .sum()
+}
+
+/// Returns the number of integer points strictly inside a simple polygon with Pick's theorem,
+/// `A = i + b/2 - 1`. See <https://en.wikipedia.org/wiki/Pick%27s_theorem>.
+pub fn interior_points(vertices: &[Point<i64>]) -> i64 {
+    (double_area(vertices) - boundary_points(vertices) + 2) / 2
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{boundary_points, double_area, interior_points, Direction, Point};
+
+    #[test]
+    fn measures_distances() {
+        let a = Point::new(1usize, 6);
+        let b = Point::new(5usize, 11);
+        assert_eq!(a.manhattan_distance(b), 9);
+        assert_eq!(b.manhattan_distance(a), 9);
+        assert_eq!(a.chebyshev_distance(b), 5);
+
+        let c = Point::new(-2i64, 3);
+        assert_eq!(c.manhattan_distance(Point::default()), 5);
+        assert_eq!(c + Point::new(2, -3), Point::default());
+        assert_eq!(-c * 2, Point::new(4, -6));
+    }
+
+    #[test]
+    fn turns_directions() {
+        assert_eq!(Direction::North.turn_right(), Direction::East);
+        assert_eq!(Direction::North.turn_left(), Direction::West);
+        assert_eq!(Direction::NorthEast.turn_right(), Direction::SouthEast);
+        assert_eq!(Direction::SouthWest.reverse(), Direction::NorthEast);
+        assert!(Direction::NorthWest.is_diagonal());
+        assert!(!Direction::South.is_diagonal());
+
+        for direction in Direction::ALL {
+            assert_eq!(direction.reverse().delta(), -direction.delta());
+            assert_eq!(direction.turn_left().turn_right(), direction);
+        }
+    }
+
+    #[test]
+    fn steps_points() {
+        let point = Point::new(0usize, 1);
+        assert_eq!(point.step(Direction::North), Some(Point::new(0, 0)));
+        assert_eq!(point.step(Direction::SouthEast), Some(Point::new(1, 2)));
+        assert_eq!(point.step(Direction::West), None);
+        assert_eq!(Point::from_position((1, 0)), point);
+        assert_eq!(point.position(), (1, 0));
+        assert_eq!(Direction::East.offset(), (0, 1));
+    }
+
+    #[test]
+    fn measures_polygons() {
+        // a 4x3 rectangle, listed counterclockwise.
+        let rectangle = [
+            Point::new(0, 0),
+            Point::new(0, 3),
+            Point::new(4, 3),
+            Point::new(4, 0),
+        ];
+        assert_eq!(double_area(&rectangle), 24);
+        assert_eq!(boundary_points(&rectangle), 14);
+        assert_eq!(interior_points(&rectangle), 6);
+
+        let triangle = [Point::new(0, 0), Point::new(2, 0), Point::new(0, 1)];
+        assert_eq!(double_area(&triangle), 2);
+        assert_eq!(interior_points(&triangle), 0);
+    }
+}
@@ -0,0 +1,439 @@
+/// A two-dimensional grid of cells, e.g. the map or image of a puzzle input.
+///
+/// Cells are stored in a single `Vec` in row-major order and addressed by a `(row, column)`
+/// [`Position`]. Lookups and neighbors are bounds-checked, so solutions do not need to guard
+/// against the edges of the grid themselves.
+use std::{
+    convert::Infallible,
+    error::Error,
+    fmt::Display,
+    ops::{Index, IndexMut},
+    str::FromStr,
+};
+
+use crate::geometry::Direction;
+
+/// The row and column of a cell, starting at the top left.
+pub type Position = (usize, usize);
+
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>,
+}
+
+impl<T> Grid<T> {
+    /// Creates a grid from cells in row-major order.
+    ///
+    /// # Panics
+    /// Panics if the number of cells does not match the dimensions.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Self {
+        assert_eq!(
+            cells.len(),
+            width * height,
+            "expected {width}x{height} cells, got {}",
+            cells.len()
+        );
+        Self {
+            width,
+            height,
+            cells,
+        }
+    }
+
+    /// Creates a grid where every cell is set to `value`.
+    pub fn filled(width: usize, height: usize, value: T) -> Self
+    where
+        T: Clone,
+    {
+        Self::new(width, height, vec![value; width * height])
+    }
+
+    /// Parses a grid with one line per row, mapping every character to a cell.
+    pub fn parse(input: &str, mut f: impl FnMut(char) -> T) -> Result<Self, ParseGridError> {
+        Self::try_parse(input, |c| Ok::<_, Infallible>(f(c)))
+    }
+
+    /// Parses a grid with one line per row, mapping every character to a cell with a fallible
+    /// function, e.g. `Tile::try_from`.
+    pub fn try_parse<E>(
+        input: &str,
+        mut f: impl FnMut(char) -> Result<T, E>,
+    ) -> Result<Self, ParseGridError<E>> {
+        let mut cells = Vec::with_capacity(input.len());
+        let mut width = None;
+        let mut height = 0;
+
+        for (row, line) in input.lines().enumerate() {
+            let start = cells.len();
+            for (column, c) in line.chars().enumerate() {
+                let cell = f(c).map_err(|source| ParseGridError::Cell {
+                    position: (row, column),
+                    source,
+                })?;
+                cells.push(cell);
+            }
+
+            let found = cells.len() - start;
+            match width {
+                Some(expected) if expected != found => {
+                    return Err(ParseGridError::Ragged {
+                        row,
+                        expected,
+                        found,
+                    });
+                }
+                _ => width = Some(found),
+            }
+            height += 1;
+        }
+
+        Ok(Self::new(width.unwrap_or(0), height, cells))
+    }
+
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Returns `true` if the position lies within the grid.
+    pub fn contains(&self, (row, column): Position) -> bool {
+        row < self.height && column < self.width
+    }
+
+    pub fn get(&self, position: Position) -> Option<&T> {
+        self.contains(position)
+            .then(|| &self.cells[self.index_of(position)])
+    }
+
+    pub fn get_mut(&mut self, position: Position) -> Option<&mut T> {
+        if self.contains(position) {
+            let index = self.index_of(position);
+            Some(&mut self.cells[index])
+        } else {
+            None
+        }
+    }
+
+    /// Moves a position by an offset, returns [`None`] if it leaves the grid.
+    pub fn step(
+        &self,
+        (row, column): Position,
+        (d_row, d_column): (isize, isize),
+    ) -> Option<Position> {
+        let position = (
+            row.checked_add_signed(d_row)?,
+            column.checked_add_signed(d_column)?,
+        );
+        self.contains(position).then_some(position)
+    }
+
+    /// The positions of the up to four cells sharing an edge with a position, clockwise starting
+    /// above it.
+    pub fn neighbors(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::CARDINAL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction.offset()))
+    }
+
+    /// The positions of the up to eight cells sharing an edge or a corner with a position,
+    /// clockwise starting above it.
+    pub fn adjacent(&self, position: Position) -> impl Iterator<Item = Position> + '_ {
+        Direction::ALL
+            .into_iter()
+            .filter_map(move |direction| self.step(position, direction.offset()))
+    }
+
+    /// Iterates the cells in row-major order.
+    pub fn iter(&self) -> std::slice::Iter<'_, T> {
+        self.cells.iter()
+    }
+
+    /// Iterates the cells with their positions in row-major order.
+    pub fn enumerate(&self) -> impl Iterator<Item = (Position, &T)> + '_ {
+        self.positions().zip(&self.cells)
+    }
+
+    /// Iterates all positions in row-major order.
+    pub fn positions(&self) -> impl Iterator<Item = Position> {
+        let width = self.width;
+        (0..self.cells.len()).map(move |index| (index / width, index % width))
+    }
+
+    /// Returns the position of the first cell that matches a predicate, in row-major order.
+    pub fn position(&self, mut predicate: impl FnMut(&T) -> bool) -> Option<Position> {
+        self.enumerate()
+            .find_map(|(position, cell)| predicate(cell).then_some(position))
+    }
+
+    /// Returns the cells of a row.
+    ///
+    /// # Panics
+    /// Panics if the row is out of bounds.
+    pub fn row(&self, row: usize) -> &[T] {
+        assert!(row < self.height, "row {row} is out of bounds");
+        &self.cells[row * self.width..(row + 1) * self.width]
+    }
+
+    /// Returns the cells of a column, from top to bottom.
+    ///
+    /// # Panics
+    /// Panics if the column is out of bounds.
+    pub fn column(&self, column: usize) -> impl Iterator<Item = &T> + '_ {
+        assert!(column < self.width, "column {column} is out of bounds");
+        self.cells.iter().skip(column).step_by(self.width)
+    }
+
+    pub fn rows(&self) -> impl Iterator<Item = &[T]> + '_ {
+        // NOTE: `chunks_exact` panics on a chunk size of zero.
+        self.cells.chunks_exact(self.width.max(1))
+    }
+
+    pub fn columns(&self) -> impl Iterator<Item = impl Iterator<Item = &T> + '_> + '_ {
+        (0..self.width).map(|column| self.column(column))
+    }
+
+    /// Creates a grid of the same size by mapping every cell.
+    pub fn map<U>(&self, f: impl FnMut(&T) -> U) -> Grid<U> {
+        Grid::new(self.width, self.height, self.cells.iter().map(f).collect())
+    }
+
+    /// Mirrors the grid along its main diagonal, rows become columns.
+    pub fn transpose(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.swap_axes(|(row, column)| (column, row))
+    }
+
+    /// Rotates the grid by 90 degrees clockwise.
+    pub fn rotate_clockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.swap_axes(|(row, column)| (self.height - 1 - column, row))
+    }
+
+    /// Rotates the grid by 90 degrees counterclockwise.
+    pub fn rotate_counterclockwise(&self) -> Self
+    where
+        T: Clone,
+    {
+        self.swap_axes(|(row, column)| (column, self.width - 1 - row))
+    }
+
+    /// Builds a grid with the width and height swapped, taking each cell from the position in
+    /// `self` that `source` maps its position to.
+    fn swap_axes(&self, source: impl Fn(Position) -> Position) -> Self
+    where
+        T: Clone,
+    {
+        let (width, height) = (self.height, self.width);
+        let cells = (0..self.cells.len())
+            .map(|index| self[source((index / width, index % width))].clone())
+            .collect();
+        Self::new(width, height, cells)
+    }
+
+    fn index_of(&self, (row, column): Position) -> usize {
+        row * self.width + column
+    }
+}
+
+impl<T> Index<Position> for Grid<T> {
+    type Output = T;
+
+    fn index(&self, position: Position) -> &Self::Output {
+        self.get(position).unwrap_or_else(|| {
+            panic!(
+                "position {position:?} is out of bounds of a {}x{} grid",
+                self.width, self.height
+            )
+        })
+    }
+}
+
+impl<T> IndexMut<Position> for Grid<T> {
+    fn index_mut(&mut self, position: Position) -> &mut Self::Output {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(position).unwrap_or_else(|| {
+            panic!("position {position:?} is out of bounds of a {width}x{height} grid")
+        })
+    }
+}
+
+impl FromStr for Grid<char> {
+    type Err = ParseGridError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s, |c| c)
+    }
+}
+
+/// Prints one line per row, e.g. to debug a solution.
+impl<T: Display> Display for Grid<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        for row in self.rows() {
+            for cell in row {
+                write!(f, "{cell}")?;
+            }
+            writeln!(f)?;
+        }
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// An error which can be returned when parsing a [`Grid`].
+#[derive(Debug, PartialEq, Eq)]
+pub enum ParseGridError<E = Infallible> {
+    /// A row has a different length than the first row.
+    Ragged {
+        row: usize,
+        expected: usize,
+        found: usize,
+    },
+    /// A character could not be mapped to a cell.
+    Cell { position: Position, source: E },
+}
+
+impl<E: Error + 'static> Error for ParseGridError<E> {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            ParseGridError::Ragged { .. } => None,
+            ParseGridError::Cell { source, .. } => Some(source),
+        }
+    }
+}
+
+impl<E: Display> Display for ParseGridError<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            ParseGridError::Ragged {
+                row,
+                expected,
+                found,
+            } => write!(
+                f,
+                "expecting row {row} to have {expected} cells, found {found}"
+            ),
+            ParseGridError::Cell {
+                position: (row, column),
+                source,
+            } => write!(f, "invalid cell at row {row}, column {column}: {source}"),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Grid, ParseGridError};
+
+    fn grid() -> Grid<char> {
+        "abc\ndef".parse().unwrap()
+    }
+
+    #[test]
+    fn parses_grids() {
+        let grid = grid();
+        assert_eq!((grid.width(), grid.height()), (3, 2));
+        assert_eq!(grid[(1, 0)], 'd');
+        assert_eq!(grid.get((2, 0)), None);
+        assert_eq!(grid.get((0, 3)), None);
+        assert_eq!(grid.to_string(), "abc\ndef\n");
+
+        let digits = Grid::parse("12\n34\n", |c| c.to_digit(10).unwrap()).unwrap();
+        assert_eq!(digits.iter().sum::<u32>(), 10);
+
+        let empty: Grid<char> = "".parse().unwrap();
+        assert_eq!((empty.width(), empty.height()), (0, 0));
+        assert_eq!(empty.rows().count(), 0);
+    }
+
+    #[test]
+    fn rejects_invalid_grids() {
+        assert_eq!(
+            "abc\nde".parse::<Grid<char>>(),
+            Err(ParseGridError::Ragged {
+                row: 1,
+                expected: 3,
+                found: 2
+            })
+        );
+
+        let result = Grid::try_parse("..\n.x", |c| match c {
+            '.' => Ok(0),
+            c => Err(c),
+        });
+        assert_eq!(
+            result,
+            Err(ParseGridError::Cell {
+                position: (1, 1),
+                source: 'x'
+            })
+        );
+    }
+
+    #[test]
+    fn finds_neighbors_within_bounds() {
+        let grid = Grid::filled(3, 3, 0);
+
+        assert_eq!(
+            grid.neighbors((0, 0)).collect::<Vec<_>>(),
+            vec![(0, 1), (1, 0)]
+        );
+        assert_eq!(grid.neighbors((1, 1)).count(), 4);
+        assert_eq!(
+            grid.adjacent((2, 2)).collect::<Vec<_>>(),
+            vec![(1, 2), (2, 1), (1, 1)]
+        );
+        assert_eq!(grid.adjacent((1, 1)).count(), 8);
+        assert_eq!(grid.step((0, 2), (0, 1)), None);
+        assert_eq!(grid.step((0, 2), (2, -2)), Some((2, 0)));
+    }
+
+    #[test]
+    fn iterates_rows_and_columns() {
+        let grid = grid();
+
+        assert_eq!(grid.row(1), ['d', 'e', 'f']);
+        assert_eq!(grid.rows().count(), 2);
+        assert_eq!(grid.column(1).collect::<String>(), "be");
+        assert_eq!(
+            grid.columns()
+                .map(|column| column.collect::<String>())
+                .collect::<Vec<_>>(),
+            vec!["ad", "be", "cf"]
+        );
+        assert_eq!(grid.position(|&c| c == 'e'), Some((1, 1)));
+        assert_eq!(grid.enumerate().nth(4), Some(((1, 1), &'e')));
+    }
+
+    #[test]
+    fn transforms_grids() {
+        let grid = grid();
+
+        assert_eq!(grid.transpose().to_string(), "ad\nbe\ncf\n");
+        assert_eq!(grid.rotate_clockwise().to_string(), "da\neb\nfc\n");
+        assert_eq!(grid.rotate_counterclockwise().to_string(), "cf\nbe\nad\n");
+        assert_eq!(
+            grid.rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise()
+                .rotate_clockwise(),
+            grid
+        );
+        assert_eq!(grid.map(|c| c.to_ascii_uppercase()).row(0), ['A', 'B', 'C']);
+
+        let mut grid = grid;
+        grid[(0, 0)] = 'x';
+        assert_eq!(grid.row(0), ['x', 'b', 'c']);
+    }
+}
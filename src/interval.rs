@@ -0,0 +1,418 @@
+/// Arithmetic on sets of integer ranges and on functions that shift some of those ranges.
+///
+/// Ranges are half-open, like [`Range`], so that adjacent ranges share a bound and no value
+/// needs a successor. Use a wider type than the puzzle if a range has to include its maximum,
+/// e.g. `u64` for `u32` values.
+use std::ops::{Add, Range, Sub};
+
+/// Returns the values contained in both ranges, if there are any.
+pub fn overlap<T: Copy + Ord>(a: &Range<T>, b: &Range<T>) -> Option<Range<T>> {
+    let range = a.start.max(b.start)..a.end.min(b.end);
+    (!range.is_empty()).then_some(range)
+}
+
+/// A set of values, stored as sorted, disjoint and non-adjacent ranges.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RangeSet<T> {
+    ranges: Vec<Range<T>>,
+}
+
+impl<T> Default for RangeSet<T> {
+    fn default() -> Self {
+        Self { ranges: Vec::new() }
+    }
+}
+
+impl<T: Copy + Ord> RangeSet<T> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the values of a range, merging it with the ranges it overlaps or touches.
+    pub fn insert(&mut self, range: Range<T>) {
+        if range.is_empty() {
+            return;
+        }
+
+        // NOTE: the ranges that end before the new one starts stay in place, as do the ranges
+        // that start after it ends. Everything in between is merged into a single range.
+        let first = self.ranges.partition_point(|r| r.end < range.start);
+        let last = self.ranges.partition_point(|r| r.start <= range.end);
+
+        let merged = match self.ranges[first..last] {
+            [] => range,
+            ref touched => {
+                range.start.min(touched[0].start)..range.end.max(touched[touched.len() - 1].end)
+            }
+        };
+        self.ranges.splice(first..last, [merged]);
+    }
+
+    pub fn contains(&self, value: &T) -> bool {
+        let index = self.ranges.partition_point(|r| r.end <= *value);
+        self.ranges
+            .get(index)
+            .is_some_and(|range| range.contains(value))
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.ranges.is_empty()
+    }
+
+    /// The smallest value of the set.
+    pub fn min(&self) -> Option<T> {
+        self.ranges.first().map(|range| range.start)
+    }
+
+    /// The ranges of the set in ascending order.
+    pub fn ranges(&self) -> &[Range<T>] {
+        &self.ranges
+    }
+
+    /// Returns the values contained in either set.
+    pub fn union(&self, other: &Self) -> Self {
+        let mut set = self.clone();
+        set.extend(other.ranges.iter().cloned());
+        set
+    }
+
+    /// Returns the values contained in both sets.
+    pub fn intersection(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let (mut i, mut j) = (0, 0);
+
+        while let (Some(a), Some(b)) = (self.ranges.get(i), other.ranges.get(j)) {
+            ranges.extend(overlap(a, b));
+            if a.end < b.end {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+
+        Self { ranges }
+    }
+
+    /// Returns the values of this set that are not contained in the other set.
+    pub fn difference(&self, other: &Self) -> Self {
+        let mut ranges = Vec::new();
+        let mut others = other.ranges.iter().peekable();
+
+        for range in &self.ranges {
+            let mut start = range.start;
+
+            // skip the ranges that end before this one starts, they cannot remove anything.
+            while others.next_if(|r| r.end <= start).is_some() {}
+
+            while let Some(removed) = others.peek() {
+                if removed.start >= range.end {
+                    break;
+                }
+                if start < removed.start {
+                    ranges.push(start..removed.start);
+                }
+                start = start.max(removed.end);
+                if removed.end > range.end {
+                    break;
+                }
+                others.next();
+            }
+
+            if start < range.end {
+                ranges.push(start..range.end);
+            }
+        }
+
+        Self { ranges }
+    }
+}
+
+impl<T: Copy + Ord> From<Range<T>> for RangeSet<T> {
+    fn from(range: Range<T>) -> Self {
+        Self::from_iter([range])
+    }
+}
+
+impl<T: Copy + Ord> FromIterator<Range<T>> for RangeSet<T> {
+    fn from_iter<I: IntoIterator<Item = Range<T>>>(iter: I) -> Self {
+        let mut set = Self::new();
+        set.extend(iter);
+        set
+    }
+}
+
+impl<T: Copy + Ord> Extend<Range<T>> for RangeSet<T> {
+    fn extend<I: IntoIterator<Item = Range<T>>>(&mut self, iter: I) {
+        for range in iter {
+            self.insert(range);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// A piecewise-linear function that shifts the values of some source ranges to a destination
+/// and maps every other value to itself, e.g. one of the almanac maps of 2023 day 5.
+#[derive(Clone, Debug, PartialEq, Eq, Hash)]
+pub struct RangeMap<T> {
+    /// Source ranges and the destination of their start, sorted by source.
+    shifts: Vec<(Range<T>, T)>,
+}
+
+impl<T> Default for RangeMap<T> {
+    fn default() -> Self {
+        Self { shifts: Vec::new() }
+    }
+}
+
+impl<T> RangeMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Maps the values of `source` to the range starting at `destination`.
+    ///
+    /// # Panics
+    /// Panics if the source overlaps the source of a previously inserted range.
+    pub fn insert(&mut self, source: Range<T>, destination: T) {
+        if source.is_empty() {
+            return;
+        }
+
+        let index = self.shifts.partition_point(|(s, _)| s.end <= source.start);
+        if let Some((next, _)) = self.shifts.get(index) {
+            assert!(
+                next.start >= source.end,
+                "source ranges of a RangeMap may not overlap"
+            );
+        }
+        self.shifts.insert(index, (source, destination));
+    }
+
+    /// Returns the value that `value` maps to.
+    pub fn get(&self, value: T) -> T {
+        let index = self.shifts.partition_point(|(s, _)| s.end <= value);
+        match self.shifts.get(index) {
+            Some((source, destination)) if source.contains(&value) => {
+                *destination + (value - source.start)
+            }
+            _ => value,
+        }
+    }
+
+    /// Splits a range at the bounds of the source ranges and maps every piece, in the order of
+    /// the values they were mapped from. The pieces may overlap.
+    pub fn map_range(&self, range: Range<T>) -> Vec<Range<T>> {
+        self.split(&range)
+            .into_iter()
+            .map(|piece| {
+                let start = self.get(piece.start);
+                start..start + (piece.end - piece.start)
+            })
+            .collect()
+    }
+
+    /// Returns the set of values that the values of `set` map to.
+    pub fn map_set(&self, set: &RangeSet<T>) -> RangeSet<T> {
+        set.ranges()
+            .iter()
+            .flat_map(|range| self.map_range(range.clone()))
+            .collect()
+    }
+
+    /// Composes two maps into one that maps a value like `next.get(self.get(value))`.
+    pub fn then(&self, next: &Self) -> Self {
+        let mut composed = Self::new();
+        let mut shift = |source: Range<T>, destination: T| {
+            // NOTE: pieces that end up where they started are the identity, no need to store them.
+            if source.start != destination {
+                composed.insert(source, destination);
+            }
+        };
+
+        // values shifted by this map, then by the next map if they land in one of its sources.
+        for (source, destination) in &self.shifts {
+            let target = *destination..*destination + (source.end - source.start);
+            for piece in next.split(&target) {
+                let from = source.start + (piece.start - *destination);
+                shift(
+                    from..from + (piece.end - piece.start),
+                    next.get(piece.start),
+                );
+            }
+        }
+
+        // values that this map leaves in place, shifted by the next map only.
+        let shifted: RangeSet<T> = self.shifts.iter().map(|(s, _)| s.clone()).collect();
+        for (source, destination) in &next.shifts {
+            let unshifted = RangeSet::from(source.clone()).difference(&shifted);
+            for piece in unshifted.ranges() {
+                shift(piece.clone(), *destination + (piece.start - source.start));
+            }
+        }
+
+        composed
+    }
+
+    /// Splits a range at the bounds of the source ranges, without mapping the pieces.
+    fn split(&self, range: &Range<T>) -> Vec<Range<T>> {
+        let mut pieces = Vec::new();
+        let mut start = range.start;
+
+        for (source, _) in &self.shifts {
+            if let Some(shared) = overlap(source, range) {
+                if start < shared.start {
+                    pieces.push(start..shared.start);
+                }
+                pieces.push(shared.clone());
+                start = shared.end;
+            }
+        }
+
+        if start < range.end {
+            pieces.push(start..range.end);
+        }
+        pieces
+    }
+}
+
+impl<T> FromIterator<(Range<T>, T)> for RangeMap<T>
+where
+    T: Copy + Ord + Add<Output = T> + Sub<Output = T>,
+{
+    fn from_iter<I: IntoIterator<Item = (Range<T>, T)>>(iter: I) -> Self {
+        let mut map = Self::new();
+        for (source, destination) in iter {
+            map.insert(source, destination);
+        }
+        map
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{collections::BTreeSet, ops::Range};
+
+    use proptest::{collection::vec, prelude::*};
+
+    use super::{RangeMap, RangeSet};
+
+    /// Starts of the generated ranges, small enough to enumerate every value that can be hit.
+    const DOMAIN: Range<u32> = 0..64;
+    const VALUES: Range<u32> = 0..128;
+
+    fn values(set: &RangeSet<u32>) -> BTreeSet<u32> {
+        VALUES.filter(|value| set.contains(value)).collect()
+    }
+
+    fn ranges() -> impl Strategy<Value = Vec<Range<u32>>> {
+        vec(
+            (DOMAIN, 0..16u32).prop_map(|(start, len)| start..start + len),
+            0..8,
+        )
+    }
+
+    /// Maps with non-overlapping sources, built by cutting the domain into consecutive pieces.
+    fn range_map() -> impl Strategy<Value = RangeMap<u32>> {
+        vec((1..12u32, any::<bool>(), DOMAIN), 0..8).prop_map(|pieces| {
+            let mut start = 0;
+            let mut map = RangeMap::new();
+            for (len, is_shifted, destination) in pieces {
+                if is_shifted {
+                    map.insert(start..start + len, destination);
+                }
+                start += len;
+            }
+            map
+        })
+    }
+
+    #[test]
+    fn merges_ranges() {
+        let set: RangeSet<u32> = [5..8, 1..3, 3..4, 10..12, 7..10].into_iter().collect();
+        assert_eq!(set.ranges(), [1..4, 5..12]);
+        assert_eq!(set.min(), Some(1));
+        assert!(set.contains(&11));
+        assert!(!set.contains(&4));
+    }
+
+    #[test]
+    fn maps_almanac_ranges() {
+        // seed-to-soil and soil-to-fertilizer maps of 2023 day 5.
+        let seed_to_soil: RangeMap<u64> = [(98..100, 50), (50..98, 52)].into_iter().collect();
+        let soil_to_fertilizer: RangeMap<u64> = [(15..52, 0), (52..54, 37), (0..15, 39)]
+            .into_iter()
+            .collect();
+
+        assert_eq!(seed_to_soil.get(79), 81);
+        assert_eq!(seed_to_soil.get(14), 14);
+        assert_eq!(
+            seed_to_soil.map_range(90..102),
+            vec![92..100, 50..52, 100..102]
+        );
+
+        let composed = seed_to_soil.then(&soil_to_fertilizer);
+        for seed in [79, 14, 55, 13, 99] {
+            assert_eq!(
+                composed.get(seed),
+                soil_to_fertilizer.get(seed_to_soil.get(seed))
+            );
+        }
+    }
+
+    proptest! {
+        #[test]
+        fn set_operations_match_brute_force(a in ranges(), b in ranges()) {
+            let (a_set, b_set): (RangeSet<u32>, RangeSet<u32>) =
+                (a.iter().cloned().collect(), b.iter().cloned().collect());
+            let (a_values, b_values): (BTreeSet<u32>, BTreeSet<u32>) =
+                (a.into_iter().flatten().collect(), b.into_iter().flatten().collect());
+
+            prop_assert_eq!(values(&a_set), a_values.clone());
+            prop_assert_eq!(
+                values(&a_set.union(&b_set)),
+                &a_values | &b_values
+            );
+            prop_assert_eq!(
+                values(&a_set.intersection(&b_set)),
+                &a_values & &b_values
+            );
+            prop_assert_eq!(
+                values(&a_set.difference(&b_set)),
+                &a_values - &b_values
+            );
+
+            // the ranges stay sorted, disjoint and non-adjacent.
+            for set in [a_set.union(&b_set), a_set.intersection(&b_set), a_set.difference(&b_set)] {
+                prop_assert!(set.ranges().iter().all(|r| !r.is_empty()));
+                prop_assert!(set.ranges().windows(2).all(|w| w[0].end < w[1].start));
+            }
+        }
+
+        #[test]
+        fn map_operations_match_brute_force(
+            first in range_map(),
+            second in range_map(),
+            set in ranges(),
+        ) {
+            let set: RangeSet<u32> = set.into_iter().collect();
+
+            let mapped: BTreeSet<u32> = values(&set).into_iter().map(|v| first.get(v)).collect();
+            let mapped_set = first.map_set(&set);
+            prop_assert_eq!(
+                mapped_set.ranges().iter().cloned().flatten().collect::<BTreeSet<_>>(),
+                mapped
+            );
+
+            let composed = first.then(&second);
+            for value in VALUES {
+                prop_assert_eq!(composed.get(value), second.get(first.get(value)));
+            }
+        }
+    }
+}
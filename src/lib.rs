@@ -1,3 +1,14 @@
+pub mod cycle;
+pub mod geometry;
+pub mod grid;
+pub mod interval;
+pub mod math;
 pub mod template;
 
+// NOTE: the allocator lives in the library so that every binary linking it is profiled,
+// including the runner binary that runs all solutions in-process.
+#[cfg(feature = "dhat-heap")]
+#[global_allocator]
+static ALLOC: dhat::Alloc = dhat::Alloc;
+
 // Use this file to add helper functions and additional modules.
@@ -1,40 +1,43 @@
-use advent_of_code::template::commands::{all, download, read, scaffold, solve, time};
+use advent_of_code::template::commands::{download, read, scaffold, solve};
+use advent_of_code::template::config;
+use advent_of_code::template::error::TemplateError;
 use args::{parse, AppArguments};
+use std::env;
 
 #[cfg(feature = "today")]
-use advent_of_code::template::Day;
-#[cfg(feature = "today")]
-use std::process;
+use advent_of_code::template::{Day, PuzzleId, Year};
 
 mod args {
-    use advent_of_code::template::Day;
-    use std::process;
-
+    use advent_of_code::template::{
+        runner::{parse_seconds, OutputFormat},
+        PuzzleId, Year,
+    };
+    use std::time::Duration;
     pub enum AppArguments {
         Download {
-            day: Day,
+            id: PuzzleId,
+            force: bool,
         },
         Read {
-            day: Day,
+            id: PuzzleId,
         },
         Scaffold {
-            day: Day,
+            id: PuzzleId,
             download: bool,
+            force: bool,
+            preset: Option<String>,
         },
         Solve {
-            day: Day,
+            id: PuzzleId,
             release: bool,
             dhat: bool,
             submit: Option<u8>,
+            force: bool,
+            format: Option<OutputFormat>,
+            timeout: Option<Duration>,
         },
-        All {
-            release: bool,
-        },
-        Time {
-            all: bool,
-            day: Option<Day>,
-            store: bool,
-        },
+        /// `all`, `time` and `verify`, which are forwarded to the `runner` binary.
+        Runner,
         #[cfg(feature = "today")]
         Today,
     }
@@ -42,46 +45,38 @@ mod args {
     pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
         let mut args = pico_args::Arguments::from_env();
 
-        let app_args = match args.subcommand()?.as_deref() {
-            Some("all") => AppArguments::All {
-                release: args.contains("--release"),
-            },
-            Some("time") => {
-                let all = args.contains("--all");
-                let store = args.contains("--store");
+        let subcommand = args.subcommand()?;
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
 
-                AppArguments::Time {
-                    all,
-                    day: args.opt_free_from_str()?,
-                    store,
-                }
-            }
+        let app_args = match subcommand.as_deref() {
+            // NOTE: the runner parses the arguments of these commands.
+            Some("all" | "time" | "verify") => return Ok(AppArguments::Runner),
             Some("download") => AppArguments::Download {
-                day: args.free_from_str()?,
+                id: PuzzleId::with_year_or_configured(year, args.free_from_str()?)?,
+                force: args.contains("--force"),
             },
             Some("read") => AppArguments::Read {
-                day: args.free_from_str()?,
+                id: PuzzleId::with_year_or_configured(year, args.free_from_str()?)?,
             },
             Some("scaffold") => AppArguments::Scaffold {
-                day: args.free_from_str()?,
+                id: PuzzleId::with_year_or_configured(year, args.free_from_str()?)?,
                 download: args.contains("--download"),
+                force: args.contains("--force"),
+                preset: args.opt_value_from_str("--preset")?,
             },
             Some("solve") => AppArguments::Solve {
-                day: args.free_from_str()?,
+                id: PuzzleId::with_year_or_configured(year, args.free_from_str()?)?,
                 release: args.contains("--release"),
                 submit: args.opt_value_from_str("--submit")?,
                 dhat: args.contains("--dhat"),
+                force: args.contains("--force"),
+                format: args.opt_value_from_str("--format")?,
+                timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
             },
             #[cfg(feature = "today")]
             Some("today") => AppArguments::Today,
-            Some(x) => {
-                eprintln!("Unknown command: {x}");
-                process::exit(1);
-            }
-            None => {
-                eprintln!("No command specified.");
-                process::exit(1);
-            }
+            Some(x) => return Err(format!("unknown command `{x}`.").into()),
+            None => return Err("no command specified.".into()),
         };
 
         let remaining = args.finish();
@@ -94,45 +89,71 @@ mod args {
 }
 
 fn main() {
-    match parse() {
-        Err(err) => {
-            eprintln!("Error: {err}");
-            std::process::exit(1);
+    // NOTE: read the configuration up front so that a malformed file is reported before anything runs.
+    config::get();
+
+    let args = parse().unwrap_or_else(|err| TemplateError::Usage(err.to_string()).exit());
+
+    let result = match args {
+        AppArguments::Runner => forward_to_runner(),
+        AppArguments::Download { id, force } => download::handle(id, force),
+        AppArguments::Read { id } => read::handle(id),
+        AppArguments::Scaffold {
+            id,
+            download,
+            force,
+            preset,
+        } => {
+            // NOTE: download first, so that the scaffold can use the puzzle description.
+            let downloaded = if download {
+                download::handle(id, force)
+            } else {
+                Ok(())
+            };
+            downloaded.and_then(|()| scaffold::handle(id, preset.as_deref()))
         }
-        Ok(args) => match args {
-            AppArguments::All { release } => all::handle(release),
-            AppArguments::Time { day, all, store } => time::handle(day, all, store),
-            AppArguments::Download { day } => download::handle(day),
-            AppArguments::Read { day } => read::handle(day),
-            AppArguments::Scaffold { day, download } => {
-                scaffold::handle(day);
-                if download {
-                    download::handle(day);
-                }
-            }
-            AppArguments::Solve {
-                day,
-                release,
-                dhat,
-                submit,
-            } => solve::handle(day, release, dhat, submit),
-            #[cfg(feature = "today")]
-            AppArguments::Today => {
-                match Day::today() {
-                    Some(day) => {
-                        scaffold::handle(day);
-                        download::handle(day);
-                        read::handle(day)
-                    }
-                    None => {
-                        eprintln!(
-                            "`today` command can only be run between the 1st and \
-                            the 25th of december. Please use `scaffold` with a specific day."
-                        );
-                        process::exit(1)
-                    }
-                };
+        AppArguments::Solve {
+            id,
+            release,
+            dhat,
+            submit,
+            force,
+            format,
+            timeout,
+        } => solve::handle(id, release, dhat, submit, force, format, timeout),
+        #[cfg(feature = "today")]
+        AppArguments::Today => match Year::today().zip(Day::today()) {
+            Some((year, day)) => {
+                let id = PuzzleId::new(year, day);
+                download::handle(id, false)
+                    .and_then(|()| scaffold::handle(id, None))
+                    .and_then(|()| read::handle(id))
             }
+            None => Err(TemplateError::Usage(
+                "`today` command can only be run between the 1st and \
+                the 25th of december. Please use `scaffold` with a specific day."
+                    .into(),
+            )),
         },
     };
+
+    if let Err(err) = result {
+        err.exit();
+    }
+}
+
+/// `all`, `time` and `verify` run the solutions in-process, so they live in the `runner` binary
+/// that includes every day. Keeping them out of this binary means that a day that does not
+/// compile only breaks those commands.
+fn forward_to_runner() -> Result<(), TemplateError> {
+    let mut cmd_args = vec!["run".to_string(), "--quiet".to_string()];
+
+    if !cfg!(debug_assertions) {
+        cmd_args.push("--release".to_string());
+    }
+
+    cmd_args.extend(["--bin".to_string(), "runner".to_string(), "--".to_string()]);
+    cmd_args.extend(env::args().skip(1));
+
+    solve::run_cargo(&cmd_args)
 }
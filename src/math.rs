@@ -0,0 +1,301 @@
+/// Number theory on integers, e.g. to combine the cycles of several ghosts or to count the
+/// integer solutions of a quadratic without rounding errors.
+///
+/// Results that may not fit in their type, e.g. a least common multiple, are returned as
+/// [`Option`]s instead of wrapping around.
+use std::ops::RangeInclusive;
+
+/// Returns the greatest common divisor, `gcd(0, 0)` is `0`.
+pub fn gcd(mut a: u64, mut b: u64) -> u64 {
+    while b != 0 {
+        (a, b) = (b, a % b);
+    }
+    a
+}
+
+/// Returns the least common multiple, or [`None`] if it does not fit in a `u64`.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Returns the greatest common divisor of all values, `0` if there are none.
+pub fn gcd_all(values: impl IntoIterator<Item = u64>) -> u64 {
+    values.into_iter().fold(0, gcd)
+}
+
+/// Returns the least common multiple of all values, `1` if there are none, or [`None`] if it
+/// does not fit in a `u64`.
+pub fn lcm_all(values: impl IntoIterator<Item = u64>) -> Option<u64> {
+    values.into_iter().try_fold(1, lcm)
+}
+
+/// Returns `(g, x, y)` such that `a·x + b·y = g`, where `g` is the non-negative greatest common
+/// divisor of `a` and `b`.
+pub fn extended_gcd(a: i64, b: i64) -> (i64, i64, i64) {
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+
+    while r != 0 {
+        let quotient = old_r / r;
+        (old_r, r) = (r, old_r - quotient * r);
+        (old_x, x) = (x, old_x - quotient * x);
+        (old_y, y) = (y, old_y - quotient * y);
+    }
+
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// Returns `base^exponent mod modulus`.
+///
+/// # Panics
+/// Panics if the modulus is `0`.
+pub fn mod_pow(base: u64, mut exponent: u64, modulus: u64) -> u64 {
+    assert_ne!(modulus, 0, "modulus must not be zero");
+
+    let modulus = u128::from(modulus);
+    let mut base = u128::from(base) % modulus;
+    let mut result = 1 % modulus;
+
+    while exponent > 0 {
+        if exponent & 1 == 1 {
+            result = result * base % modulus;
+        }
+        base = base * base % modulus;
+        exponent >>= 1;
+    }
+
+    // NOTE: the result is reduced by the modulus, which was a `u64`.
+    result as u64
+}
+
+/// Returns the `x` in `0..modulus` with `a·x ≡ 1 (mod modulus)`, or [`None`] if `a` and the
+/// modulus are not coprime.
+///
+/// # Panics
+/// Panics if the modulus is not positive.
+pub fn mod_inverse(a: i64, modulus: i64) -> Option<i64> {
+    assert!(modulus > 0, "modulus must be positive");
+    let (g, x, _) = extended_gcd(a.rem_euclid(modulus), modulus);
+    (g == 1).then(|| x.rem_euclid(modulus))
+}
+
+/// Solves a system of congruences `x ≡ residue (mod modulus)` with the Chinese remainder
+/// theorem. The moduli need not be coprime.
+///
+/// Returns the smallest non-negative solution and the modulus of all solutions, i.e. the least
+/// common multiple of the moduli. Returns [`None`] if the congruences contradict each other or
+/// the modulus does not fit in an `i64`.
+///
+/// # Panics
+/// Panics if a modulus is not positive.
+pub fn crt(congruences: impl IntoIterator<Item = (i64, i64)>) -> Option<(i64, i64)> {
+    congruences.into_iter().try_fold(
+        (0, 1),
+        |(residue, modulus), (other_residue, other_modulus)| {
+            assert!(other_modulus > 0, "moduli must be positive");
+
+            // residue + modulus·k ≡ other_residue (mod other_modulus)
+            let (g, inverse, _) = extended_gcd(modulus, other_modulus);
+            let difference = i128::from(other_residue) - i128::from(residue);
+            if difference % i128::from(g) != 0 {
+                return None;
+            }
+
+            let step = other_modulus / g;
+            let k = difference / i128::from(g) * i128::from(inverse) % i128::from(step);
+            let combined = i64::try_from(i128::from(modulus) * i128::from(step)).ok()?;
+            let solution =
+                (i128::from(residue) + i128::from(modulus) * k).rem_euclid(i128::from(combined));
+
+            // NOTE: the solution is reduced by the combined modulus, which fits in an `i64`.
+            Some((solution as i64, combined))
+        },
+    )
+}
+
+/// Returns the largest `r` with `r^k <= n`.
+///
+/// # Panics
+/// Panics if `k` is `0`.
+pub fn iroot(n: u64, k: u32) -> u64 {
+    assert_ne!(k, 0, "the zeroth root is undefined");
+    if k == 1 || n < 2 {
+        return n;
+    }
+
+    // NOTE: the float estimate is off by at most one for large values, correct it exactly.
+    let mut root = (n as f64).powf(1.0 / f64::from(k)) as u64;
+    while root.checked_pow(k).is_none_or(|power| power > n) {
+        root -= 1;
+    }
+    while (root + 1).checked_pow(k).is_some_and(|power| power <= n) {
+        root += 1;
+    }
+    root
+}
+
+/// Returns the integers strictly between the two real roots of `a·x² + b·x + c`, i.e. where
+/// its sign is the opposite of the sign of `a`. Returns [`None`] if there are none.
+///
+/// The bounds are exact, unlike rounding the roots of the quadratic formula.
+///
+/// # Panics
+/// Panics if `a` is `0`.
+pub fn between_roots(a: i64, b: i64, c: i64) -> Option<RangeInclusive<i64>> {
+    assert_ne!(a, 0, "not a quadratic");
+
+    // NOTE: flip the parabola so that it opens upwards, the integers below zero are wanted.
+    let sign = i128::from(a.signum());
+    let (a, b, c) = (
+        i128::from(a) * sign,
+        i128::from(b) * sign,
+        i128::from(c) * sign,
+    );
+    let f = |x: i128| (a * x + b) * x + c;
+    let is_between = |x: i128| f(x) < 0;
+
+    // the integer closest to the vertex has the smallest value.
+    let vertex = (-b).div_euclid(2 * a);
+    let middle = if f(vertex + 1) < f(vertex) {
+        vertex + 1
+    } else {
+        vertex
+    };
+    if !is_between(middle) {
+        return None;
+    }
+
+    // start at the roots of the quadratic formula, then correct the rounding of the square root.
+    let root = ((b * b - 4 * a * c) as u128).isqrt() as i128;
+    let mut lower = (-b - root).div_euclid(2 * a).min(middle);
+    while !is_between(lower) {
+        lower += 1;
+    }
+    while is_between(lower - 1) {
+        lower -= 1;
+    }
+
+    let mut upper = ((-b + root).div_euclid(2 * a) + 1).max(middle);
+    while !is_between(upper) {
+        upper -= 1;
+    }
+    while is_between(upper + 1) {
+        upper += 1;
+    }
+
+    Some(i64::try_from(lower).ok()?..=i64::try_from(upper).ok()?)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::{
+        between_roots, crt, extended_gcd, gcd, gcd_all, iroot, lcm, lcm_all, mod_inverse, mod_pow,
+    };
+
+    #[test]
+    fn computes_gcd_and_lcm() {
+        assert_eq!(gcd(12, 18), 6);
+        assert_eq!(gcd(0, 7), 7);
+        assert_eq!(gcd_all([12, 18, 27]), 3);
+        assert_eq!(gcd_all([]), 0);
+        assert_eq!(lcm(4, 6), Some(12));
+        assert_eq!(lcm_all([2, 3, 4]), Some(12));
+        assert_eq!(lcm_all([]), Some(1));
+        assert_eq!(lcm_all([u64::MAX, u64::MAX - 1]), None);
+    }
+
+    #[test]
+    fn computes_modular_arithmetic() {
+        assert_eq!(extended_gcd(240, 46), (2, -9, 47));
+        assert_eq!(mod_pow(4, 13, 497), 445);
+        assert_eq!(mod_pow(u64::MAX, u64::MAX, u64::MAX - 1), 1);
+        assert_eq!(mod_pow(5, 0, 1), 0);
+        assert_eq!(mod_inverse(3, 11), Some(4));
+        assert_eq!(mod_inverse(-3, 11), Some(7));
+        assert_eq!(mod_inverse(6, 9), None);
+    }
+
+    #[test]
+    fn solves_congruences() {
+        assert_eq!(crt([(2, 3), (3, 5), (2, 7)]), Some((23, 105)));
+        // moduli that share a factor.
+        assert_eq!(crt([(3, 4), (5, 6)]), Some((11, 12)));
+        assert_eq!(crt([(0, 4), (1, 6)]), None);
+        assert_eq!(crt([]), Some((0, 1)));
+    }
+
+    #[test]
+    fn computes_integer_roots() {
+        assert_eq!(iroot(0, 2), 0);
+        assert_eq!(iroot(15, 2), 3);
+        assert_eq!(iroot(16, 2), 4);
+        assert_eq!(iroot(26, 3), 2);
+        assert_eq!(iroot(27, 3), 3);
+        assert_eq!(iroot(u64::MAX, 2), u64::from(u32::MAX));
+        assert_eq!(iroot(u64::MAX, 64), 1);
+    }
+
+    #[test]
+    fn finds_integers_between_roots() {
+        // the races of 2023 day 6: hold·(time - hold) > record.
+        assert_eq!(between_roots(-1, 7, -9), Some(2..=5));
+        assert_eq!(between_roots(-1, 30, -200), Some(11..=19));
+        assert_eq!(between_roots(1, -30, 200), Some(11..=19));
+        // a double root and no real roots.
+        assert_eq!(between_roots(1, -2, 1), None);
+        assert_eq!(between_roots(1, 0, 1), None);
+        // real roots without an integer between them.
+        assert_eq!(between_roots(4, -2, 0), None);
+    }
+
+    proptest! {
+        #[test]
+        fn between_roots_matches_brute_force(
+            a in -20i64..20,
+            b in -200i64..200,
+            c in -2000i64..2000,
+        ) {
+            prop_assume!(a != 0);
+            let expected: Vec<i64> = (-3000..3000)
+                .filter(|&x| (a * x * x + b * x + c).signum() == -a.signum())
+                .collect();
+
+            let found: Vec<i64> = between_roots(a, b, c).into_iter().flatten().collect();
+            prop_assert_eq!(found, expected);
+        }
+
+        #[test]
+        fn crt_matches_brute_force(
+            congruences in proptest::collection::vec((0i64..30, 1i64..30), 0..4),
+        ) {
+            let modulus = lcm_all(congruences.iter().map(|&(_, n)| n as u64)).unwrap() as i64;
+            let expected = (0..modulus)
+                .find(|&x| congruences.iter().all(|&(r, n)| x % n == r % n));
+
+            prop_assert_eq!(crt(congruences.clone()), expected.map(|x| (x, modulus)));
+        }
+
+        #[test]
+        fn gcd_divides_both(a in any::<i64>(), b in any::<i64>()) {
+            prop_assume!(a != i64::MIN && b != i64::MIN);
+            let (g, x, y) = extended_gcd(a, b);
+            prop_assert_eq!(g as u64, gcd(a.unsigned_abs(), b.unsigned_abs()));
+            prop_assert_eq!(
+                i128::from(a) * i128::from(x) + i128::from(b) * i128::from(y),
+                i128::from(g)
+            );
+        }
+    }
+}
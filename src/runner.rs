@@ -0,0 +1,181 @@
+//! Runs `all`, `time` and `verify`, which run every scaffolded solution in-process.
+//! The main binary forwards these commands, so that only they depend on every day compiling.
+use advent_of_code::template::commands::{all, time, verify};
+use advent_of_code::template::config;
+use advent_of_code::template::error::TemplateError;
+use args::{parse, AppArguments};
+
+/// Registry of all scaffolded solutions, generated by `build.rs`.
+mod solutions {
+    include!(concat!(env!("OUT_DIR"), "/solutions.rs"));
+}
+
+mod args {
+    use advent_of_code::template::{
+        commands::time::CompareOptions,
+        config,
+        runner::{parse_seconds, RunOptions},
+        PuzzleId, Year,
+    };
+    pub enum AppArguments {
+        All {
+            year: Option<Year>,
+            options: RunOptions,
+        },
+        Time {
+            all: bool,
+            year: Option<Year>,
+            puzzle: Option<PuzzleId>,
+            store: bool,
+            options: RunOptions,
+            compare: Option<CompareOptions>,
+            history: Option<PuzzleId>,
+            memory: bool,
+        },
+        Verify {
+            year: Option<Year>,
+            puzzle: Option<PuzzleId>,
+            options: RunOptions,
+        },
+    }
+
+    /// Reads the options shared by the commands that run solutions in-process.
+    /// Options that are not passed fall back to `aoc.toml`.
+    fn run_options(
+        args: &mut pico_args::Arguments,
+    ) -> Result<RunOptions, Box<dyn std::error::Error>> {
+        Ok(RunOptions {
+            format: args
+                .opt_value_from_str("--format")?
+                .unwrap_or(config::get().run.format),
+            timeout: args.opt_value_from_fn("--timeout", parse_seconds)?,
+            bench_budget: args.opt_value_from_fn("--bench-budget", parse_seconds)?,
+            jobs: args.opt_value_from_str("--jobs")?,
+            ..RunOptions::default()
+        })
+    }
+
+    pub fn parse() -> Result<AppArguments, Box<dyn std::error::Error>> {
+        let mut args = pico_args::Arguments::from_env();
+
+        let subcommand = args.subcommand()?;
+        let year: Option<Year> = args.opt_value_from_str("--year")?;
+
+        let app_args = match subcommand.as_deref() {
+            Some("all") => {
+                // NOTE: days run in-process, so `--release` of older versions has no effect.
+                let _ = args.contains("--release");
+                AppArguments::All {
+                    year,
+                    options: run_options(&mut args)?,
+                }
+            }
+            Some("time") => {
+                let all = args.contains("--all");
+                let store = args.contains("--store");
+                let memory = args.contains("--memory");
+                let options = run_options(&mut args)?;
+                let history = args
+                    .opt_value_from_str("--history")?
+                    .map(|day| PuzzleId::with_year_or_configured(year, day))
+                    .transpose()?;
+
+                let threshold: Option<f64> = args.opt_value_from_str("--threshold")?;
+                let fail_on_regression = args.contains("--fail-on-regression");
+                let compare = (args.contains("--compare")
+                    || threshold.is_some()
+                    || fail_on_regression)
+                    .then(|| CompareOptions {
+                        threshold: threshold.unwrap_or(CompareOptions::default().threshold),
+                        fail_on_regression,
+                    });
+
+                AppArguments::Time {
+                    all,
+                    year,
+                    puzzle: args
+                        .opt_free_from_str()?
+                        .map(|day| PuzzleId::with_year_or_configured(year, day))
+                        .transpose()?,
+                    store,
+                    options,
+                    compare,
+                    history,
+                    memory,
+                }
+            }
+            Some("verify") => AppArguments::Verify {
+                year,
+                options: run_options(&mut args)?,
+                puzzle: args
+                    .opt_free_from_str()?
+                    .map(|day| PuzzleId::with_year_or_configured(year, day))
+                    .transpose()?,
+            },
+            Some(x) => return Err(format!("unknown command `{x}`.").into()),
+            None => return Err("no command specified.".into()),
+        };
+
+        let remaining = args.finish();
+        if !remaining.is_empty() {
+            eprintln!("Warning: unknown argument(s): {remaining:?}.");
+        }
+
+        Ok(app_args)
+    }
+}
+
+fn main() {
+    // NOTE: read the configuration up front so that a malformed file is reported before anything runs.
+    config::get();
+
+    let args = parse().unwrap_or_else(|err| TemplateError::Usage(err.to_string()).exit());
+
+    let result = match args {
+        AppArguments::All { year, options } => {
+            all::handle(solutions::SOLUTIONS, year, &options);
+            Ok(())
+        }
+        AppArguments::Time {
+            history: Some(id), ..
+        } => {
+            time::handle_history(id);
+            Ok(())
+        }
+        AppArguments::Time {
+            year,
+            puzzle,
+            store,
+            options,
+            memory: true,
+            ..
+        } => time::handle_memory(solutions::SOLUTIONS, year, puzzle, store, &options),
+        AppArguments::Time {
+            year,
+            puzzle,
+            all,
+            store,
+            options,
+            compare,
+            history: None,
+            memory: false,
+        } => time::handle(
+            solutions::SOLUTIONS,
+            year,
+            puzzle,
+            all,
+            store,
+            &options,
+            compare,
+        ),
+        AppArguments::Verify {
+            year,
+            puzzle,
+            options,
+        } => verify::handle(solutions::SOLUTIONS, year, puzzle, &options),
+    };
+
+    if let Err(err) = result {
+        err.exit();
+    }
+}
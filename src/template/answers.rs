@@ -0,0 +1,217 @@
+/// Stores the accepted answers for each day, used to verify solutions after changes.
+use std::{collections::HashMap, fs, io::ErrorKind, str::FromStr};
+use tinyjson::JsonValue;
+
+use crate::template::{config, error::TemplateError, PuzzleId};
+
+static ANSWERS_FILE_NAME: &str = "answers.json";
+
+/// Represents the accepted answers for a single day.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Answer {
+    pub id: PuzzleId,
+    pub part_1: Option<String>,
+    pub part_2: Option<String>,
+}
+
+/// Represents the accepted answers for a set of days.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Answers {
+    pub data: Vec<Answer>,
+}
+
+impl Answers {
+    /// Dehydrate answers to a JSON file.
+    pub fn store_file(&self) -> Result<(), TemplateError> {
+        let json = JsonValue::from(self.clone());
+        let path = config::get().paths.data_file(ANSWERS_FILE_NAME);
+        fs::File::create(&path)
+            .and_then(|mut file| json.format_to(&mut file))
+            .map_err(|source| TemplateError::Io { path, source })
+    }
+
+    /// Rehydrate answers from a JSON file. If not present, returns empty answers.
+    pub fn read_from_file() -> Result<Self, TemplateError> {
+        let path = config::get().paths.data_file(ANSWERS_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(s) => {
+                Answers::try_from(s).map_err(|reason| TemplateError::MalformedData { path, reason })
+            }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Answers::default()),
+            Err(source) => Err(TemplateError::Io { path, source }),
+        }
+    }
+
+    /// Returns the accepted answer for a part, if recorded.
+    pub fn get(&self, id: PuzzleId, part: u8) -> Option<&str> {
+        let answer = self.data.iter().find(|a| a.id == id)?;
+        match part {
+            1 => answer.part_1.as_deref(),
+            2 => answer.part_2.as_deref(),
+            _ => None,
+        }
+    }
+
+    /// Records the accepted answer for a part, overwriting a previous answer.
+    pub fn set(&mut self, id: PuzzleId, part: u8, value: &str) {
+        let index = match self.data.iter().position(|a| a.id == id) {
+            Some(index) => index,
+            None => {
+                self.data.push(Answer {
+                    id,
+                    part_1: None,
+                    part_2: None,
+                });
+                self.data.sort_unstable_by_key(|a| a.id);
+                self.data.iter().position(|a| a.id == id).unwrap()
+            }
+        };
+
+        match part {
+            1 => self.data[index].part_1 = Some(value.into()),
+            2 => self.data[index].part_2 = Some(value.into()),
+            _ => {}
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Answers> for JsonValue {
+    fn from(value: Answers) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Answers {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Answers {
+            data: json_data
+                .iter()
+                .map(Answer::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Answer> for JsonValue {
+    fn from(value: &Answer) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        value.id.write_json(&mut map);
+        map.insert(
+            "part_1".into(),
+            value
+                .part_1
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert(
+            "part_2".into(),
+            value
+                .part_2
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Answer {
+    type Error = String;
+
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected answer to be a JSON object.")?;
+
+        let id = PuzzleId::read_json(json, "answer")?;
+
+        let part_1 = json
+            .get("part_1")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_1 to be null or string.")?;
+
+        let part_2 = json
+            .get("part_2")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected answer.part_2 to be null or string.")?;
+
+        Ok(Answer {
+            id,
+            part_1: part_1.cloned(),
+            part_2: part_2.cloned(),
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::Answers;
+    use crate::puzzle;
+
+    #[test]
+    fn handles_json_answers() {
+        let json = r#"{ "data": [{ "year": 2023, "day": "05", "part_1": "35", "part_2": null }] }"#
+            .to_string();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(puzzle!(2023, 5), 1), Some("35"));
+        assert_eq!(answers.get(puzzle!(2023, 5), 2), None);
+        assert_eq!(answers.get(puzzle!(2023, 6), 1), None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_answers() {
+        let json = r#"{ "data": [{ "day": "26", "part_1": 35 }] }"#.to_string();
+        Answers::try_from(json).unwrap();
+    }
+
+    #[test]
+    fn sets_answers() {
+        let mut answers = Answers::default();
+        answers.set(puzzle!(2023, 7), 2, "5905");
+        answers.set(puzzle!(2023, 2), 1, "8");
+        answers.set(puzzle!(2023, 7), 1, "6440");
+        answers.set(puzzle!(2023, 7), 1, "6441");
+
+        assert_eq!(answers.data.len(), 2);
+        assert_eq!(answers.data[0].id, puzzle!(2023, 2));
+        assert_eq!(answers.get(puzzle!(2023, 7), 1), Some("6441"));
+        assert_eq!(answers.get(puzzle!(2023, 7), 2), Some("5905"));
+    }
+
+    #[test]
+    fn roundtrips_answers() {
+        let mut answers = Answers::default();
+        answers.set(puzzle!(2023, 1), 1, "142");
+        let json = tinyjson::JsonValue::from(answers).stringify().unwrap();
+        let answers = Answers::try_from(json).unwrap();
+        assert_eq!(answers.get(puzzle!(2023, 1), 1), Some("142"));
+    }
+}
@@ -0,0 +1,428 @@
+/// Client for adventofcode.com, used to download inputs, read puzzles and submit answers.
+///
+/// The session cookie is read from the `AOC_SESSION` environment variable or from a
+/// `adventofcode.session` file in the home or config directory. The base URL can be
+/// overridden with `AOC_BASE_URL`, e.g. to test against a local server. Requests identify
+/// this project in their user agent, which can be replaced with `AOC_USER_AGENT`.
+///
+/// Requests are throttled to [`MIN_REQUEST_INTERVAL`] and existing inputs and puzzles are
+/// only fetched again when forced.
+use std::{
+    env,
+    fmt::Display,
+    fs,
+    io::{self, Read},
+    path::{Path, PathBuf},
+    time::Duration,
+};
+
+use crate::template::fetch_cache::{self, FetchCache, MIN_REQUEST_INTERVAL};
+use crate::template::{get_data_folder, history::format_timestamp, html, puzzle, PuzzleId};
+
+const DEFAULT_BASE_URL: &str = "https://adventofcode.com";
+const DEFAULT_USER_AGENT: &str = concat!(
+    "advent_of_code/",
+    env!("CARGO_PKG_VERSION"),
+    " (https://github.com/jbstl/aoc_2023)"
+);
+
+#[derive(Debug)]
+pub enum AocClientError {
+    SessionNotFound,
+    Request(String),
+    BadStatus(u16, String),
+    UnexpectedResponse,
+    Io(io::Error),
+}
+
+impl Display for AocClientError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            AocClientError::SessionNotFound => write!(
+                f,
+                "session cookie not found. Set AOC_SESSION or write it to ~/.adventofcode.session."
+            ),
+            AocClientError::Request(e) => write!(f, "request failed: {e}"),
+            AocClientError::BadStatus(status, body) => {
+                write!(f, "server responded with status {status}: {body}")
+            }
+            AocClientError::UnexpectedResponse => {
+                write!(f, "could not find the expected content in the response.")
+            }
+            AocClientError::Io(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl From<io::Error> for AocClientError {
+    fn from(e: io::Error) -> Self {
+        AocClientError::Io(e)
+    }
+}
+
+pub struct AocClient {
+    base_url: String,
+    session: String,
+    agent: ureq::Agent,
+    throttle: Option<Duration>,
+}
+
+impl AocClient {
+    pub fn new(base_url: &str, session: &str) -> Self {
+        Self {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            agent: ureq::AgentBuilder::new()
+                .user_agent(&user_agent())
+                .timeout(Duration::from_secs(30))
+                .redirects(0)
+                .build(),
+            throttle: None,
+        }
+    }
+
+    /// Waits at least `interval` between requests, also across separate invocations.
+    #[must_use]
+    pub fn with_throttle(mut self, interval: Duration) -> Self {
+        self.throttle = Some(interval);
+        self
+    }
+
+    /// Creates a client from the environment and the session file.
+    pub fn from_env() -> Result<Self, AocClientError> {
+        let base_url = env::var("AOC_BASE_URL").unwrap_or_else(|_| DEFAULT_BASE_URL.into());
+        let session = get_session().ok_or(AocClientError::SessionNotFound)?;
+        Ok(Self::new(&base_url, &session).with_throttle(MIN_REQUEST_INTERVAL))
+    }
+
+    /// Fetches the personal puzzle input of a day.
+    pub fn get_input(&self, id: PuzzleId) -> Result<String, AocClientError> {
+        let url = format!("{}/input", self.get_puzzle_url(id));
+        self.call(self.agent.get(&url), None)
+    }
+
+    /// Fetches the description of a day's puzzle, converted to Markdown.
+    /// Contains part two once part one has been solved.
+    pub fn get_puzzle(&self, id: PuzzleId) -> Result<String, AocClientError> {
+        let url = self.get_puzzle_url(id);
+        let body = self.call(self.agent.get(&url), None)?;
+
+        let articles = html::extract_articles(&body);
+        if articles.is_empty() {
+            return Err(AocClientError::UnexpectedResponse);
+        }
+
+        Ok(articles
+            .into_iter()
+            .map(html::to_markdown)
+            .collect::<Vec<_>>()
+            .join("\n"))
+    }
+
+    /// Submits an answer and returns the message of the response.
+    pub fn submit(&self, id: PuzzleId, part: u8, answer: &str) -> Result<String, AocClientError> {
+        let url = format!("{}/answer", self.get_puzzle_url(id));
+        let form = [("level", part.to_string()), ("answer", answer.to_string())];
+        let body = self.call(self.agent.post(&url), Some(&form))?;
+
+        html::extract_articles(&body)
+            .first()
+            .map(|article| html::to_text(article))
+            .ok_or(AocClientError::UnexpectedResponse)
+    }
+
+    fn get_puzzle_url(&self, id: PuzzleId) -> String {
+        format!("{}/{}/day/{}", self.base_url, id.year, id.day.into_inner())
+    }
+
+    fn call(
+        &self,
+        request: ureq::Request,
+        form: Option<&[(&str, String)]>,
+    ) -> Result<String, AocClientError> {
+        if let Some(interval) = self.throttle {
+            fetch_cache::wait_for_request_slot(interval);
+        }
+
+        let request = request.set("Cookie", &format!("session={}", self.session));
+
+        let response = match form {
+            Some(form) => {
+                let form: Vec<(&str, &str)> = form.iter().map(|(k, v)| (*k, v.as_str())).collect();
+                request.send_form(&form)
+            }
+            None => request.call(),
+        };
+
+        match response {
+            // adventofcode.com redirects to the front page on invalid sessions.
+            Ok(response) if response.status() >= 300 => Err(AocClientError::BadStatus(
+                response.status(),
+                "invalid session cookie?".into(),
+            )),
+            Ok(response) => {
+                let mut body = String::new();
+                response.into_reader().read_to_string(&mut body)?;
+                Ok(body)
+            }
+            Err(ureq::Error::Status(status, response)) => {
+                let body = response.into_string().unwrap_or_default();
+                Err(AocClientError::BadStatus(status, html::to_text(&body)))
+            }
+            Err(e) => Err(AocClientError::Request(e.to_string())),
+        }
+    }
+}
+
+/// Downloads the input and puzzle description of a day into the `data` folder.
+/// Files that already have content are skipped unless `force` is set.
+pub fn download(id: PuzzleId, force: bool) -> Result<(), AocClientError> {
+    let input_path = get_input_path(id);
+    let puzzle_path = get_puzzle_path(id);
+
+    let fetch_input = force || !has_content(&input_path);
+    let fetch_puzzle = force || !has_content(&puzzle_path);
+
+    if !fetch_input {
+        print_skipped(&input_path, &format!("inputs/{id}"));
+    }
+
+    if !fetch_puzzle {
+        print_skipped(&puzzle_path, &format!("puzzles/{id}"));
+    }
+
+    if !fetch_input && !fetch_puzzle {
+        return Ok(());
+    }
+
+    let client = AocClient::from_env()?;
+
+    let input = if fetch_input {
+        Some(client.get_input(id)?)
+    } else {
+        None
+    };
+
+    let puzzle = if fetch_puzzle {
+        Some(client.get_puzzle(id)?)
+    } else {
+        None
+    };
+
+    println!("---");
+
+    if let Some(input) = input {
+        write_file(&input_path, &input)?;
+        fetch_cache::record_fetch(&format!("inputs/{id}"));
+        println!(
+            "🎄 Successfully wrote input to \"{}\".",
+            input_path.display()
+        );
+    }
+
+    if let Some(puzzle) = puzzle {
+        write_file(&puzzle_path, &puzzle)?;
+        fetch_cache::record_fetch(&format!("puzzles/{id}"));
+        println!(
+            "🎄 Successfully wrote puzzle to \"{}\".",
+            puzzle_path.display()
+        );
+        write_examples(id, &puzzle)?;
+    }
+
+    Ok(())
+}
+
+/// Fetches the puzzle description of a day, stores it and prints it to the terminal.
+pub fn read(id: PuzzleId) -> Result<(), AocClientError> {
+    let client = AocClient::from_env()?;
+    let puzzle = client.get_puzzle(id)?;
+    write_file(&get_puzzle_path(id), &puzzle)?;
+    fetch_cache::record_fetch(&format!("puzzles/{id}"));
+    // NOTE: part two is only revealed after solving part one, so its example may be new.
+    write_examples(id, &puzzle)?;
+    println!("{puzzle}");
+    Ok(())
+}
+
+/// Submits an answer and returns the response message.
+pub fn submit(id: PuzzleId, part: u8, result: &str) -> Result<String, AocClientError> {
+    AocClient::from_env()?.submit(id, part, result)
+}
+
+/// Writes the examples found in a puzzle description to example files that are still empty.
+fn write_examples(id: PuzzleId, puzzle: &str) -> Result<(), AocClientError> {
+    let written = puzzle::write_examples(id, &puzzle::parse_examples(puzzle))?;
+    for path in written {
+        println!("🎄 Successfully wrote example to \"{}\".", path.display());
+    }
+    Ok(())
+}
+
+fn get_input_path(id: PuzzleId) -> PathBuf {
+    get_data_folder("inputs", id.year).join(format!("{}.txt", id.day))
+}
+
+fn get_puzzle_path(id: PuzzleId) -> PathBuf {
+    get_data_folder("puzzles", id.year).join(format!("{}.md", id.day))
+}
+
+/// Whether a file exists and is not empty. Scaffolding creates empty input files.
+fn has_content(path: &Path) -> bool {
+    fs::metadata(path).is_ok_and(|m| m.len() > 0)
+}
+
+fn print_skipped(path: &Path, cache_key: &str) {
+    let fetched_at = FetchCache::read_from_file()
+        .fetched_at
+        .get(cache_key)
+        .map(|timestamp| format!(" (fetched {} UTC)", format_timestamp(*timestamp)))
+        .unwrap_or_default();
+
+    println!(
+        "Skipping \"{}\", it already exists{fetched_at}. Pass --force to download it again.",
+        path.display()
+    );
+}
+
+fn write_file(path: &Path, contents: &str) -> Result<(), io::Error> {
+    if let Some(parent) = path.parent() {
+        fs::create_dir_all(parent)?;
+    }
+    fs::write(path, contents)
+}
+
+fn get_session() -> Option<String> {
+    if let Ok(session) = env::var("AOC_SESSION") {
+        if !session.trim().is_empty() {
+            return Some(session.trim().to_string());
+        }
+    }
+
+    let home = env::var_os("HOME")
+        .or_else(|| env::var_os("USERPROFILE"))
+        .map(PathBuf::from);
+    let config = env::var_os("XDG_CONFIG_HOME")
+        .map(PathBuf::from)
+        .or_else(|| home.as_ref().map(|home| home.join(".config")));
+
+    [
+        home.map(|home| home.join(".adventofcode.session")),
+        config.map(|config| config.join("adventofcode.session")),
+    ]
+    .into_iter()
+    .flatten()
+    .find_map(|path| fs::read_to_string(path).ok())
+    .map(|session| session.trim().to_string())
+    .filter(|session| !session.is_empty())
+}
+
+/// The user agent of requests, `AOC_USER_AGENT` if set.
+fn user_agent() -> String {
+    env::var("AOC_USER_AGENT").unwrap_or_else(|_| DEFAULT_USER_AGENT.into())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::AocClient;
+    use crate::puzzle;
+    use std::{
+        io::{BufRead, BufReader, Read, Write},
+        net::TcpListener,
+        thread,
+    };
+
+    /// Serves a single request and returns the request line, headers and body.
+    fn mock_server(status: &str, body: &str) -> (String, thread::JoinHandle<String>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let url = format!("http://{}", listener.local_addr().unwrap());
+        let response = format!(
+            "HTTP/1.1 {status}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{body}",
+            body.len()
+        );
+
+        let handle = thread::spawn(move || {
+            let (stream, _) = listener.accept().unwrap();
+            let mut reader = BufReader::new(stream);
+            let mut request = String::new();
+            let mut content_length = 0;
+
+            loop {
+                let mut line = String::new();
+                reader.read_line(&mut line).unwrap();
+                if let Some(len) = line.to_lowercase().strip_prefix("content-length:") {
+                    content_length = len.trim().parse().unwrap();
+                }
+                request.push_str(&line);
+                if line == "\r\n" {
+                    break;
+                }
+            }
+
+            let mut body = vec![0; content_length];
+            reader.read_exact(&mut body).unwrap();
+            request.push_str(&String::from_utf8(body).unwrap());
+
+            reader.get_mut().write_all(response.as_bytes()).unwrap();
+            request
+        });
+
+        (url, handle)
+    }
+
+    #[test]
+    fn downloads_inputs() {
+        let (url, server) = mock_server("200 OK", "1abc2\npqr3stu8vwx\n");
+        let client = AocClient::new(&url, "secret");
+
+        let input = client.get_input(puzzle!(2023, 1)).unwrap();
+        assert_eq!(input, "1abc2\npqr3stu8vwx\n");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("GET /2023/day/1/input HTTP/1.1"));
+        assert!(request.contains("session=secret"));
+    }
+
+    #[test]
+    fn fetches_puzzles() {
+        let (url, server) = mock_server(
+            "200 OK",
+            "<main><article class=\"day-desc\"><h2>--- Day 1: Trebuchet?! ---</h2><p>Hi.</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+
+        let puzzle = client.get_puzzle(puzzle!(2023, 1)).unwrap();
+        assert_eq!(puzzle, "## --- Day 1: Trebuchet?! ---\n\nHi.\n");
+        server.join().unwrap();
+    }
+
+    #[test]
+    fn submits_answers() {
+        let (url, server) = mock_server(
+            "200 OK",
+            "<main><article><p>That's the right answer!</p></article></main>",
+        );
+        let client = AocClient::new(&url, "secret");
+
+        let message = client.submit(puzzle!(2023, 6), 2, "71503").unwrap();
+        assert_eq!(message, "That's the right answer!");
+
+        let request = server.join().unwrap();
+        assert!(request.starts_with("POST /2023/day/6/answer HTTP/1.1"));
+        assert!(request.ends_with("level=2&answer=71503"));
+    }
+
+    #[test]
+    fn reports_bad_status() {
+        let (url, server) = mock_server("400 Bad Request", "Please log in.");
+        let client = AocClient::new(&url, "secret");
+
+        assert!(matches!(
+            client.get_input(puzzle!(2023, 1)),
+            Err(super::AocClientError::BadStatus(400, _))
+        ));
+        server.join().unwrap();
+    }
+}
@@ -1,5 +1,9 @@
-use crate::template::{all_days, run_multi::run_multi};
+use crate::template::{
+    run_multi::{get_puzzles, run_multi},
+    runner::{RunOptions, Solution},
+    Year,
+};
 
-pub fn handle(is_release: bool) {
-    run_multi(&all_days().collect(), is_release, false);
+pub fn handle(solutions: &[Solution], year: Option<Year>, options: &RunOptions) {
+    run_multi(solutions, &get_puzzles(solutions, year), options);
 }
@@ -1,69 +1,359 @@
 use std::{
-    fs::{File, OpenOptions},
-    io::Write,
-    process,
+    fs::{self, File, OpenOptions},
+    io::{self, Write},
+    path::{Path, PathBuf},
 };
 
-use crate::template::Day;
+use crate::template::{config, error::TemplateError, get_data_folder, puzzle, PuzzleId};
 
-const MODULE_TEMPLATE: &str =
-    include_str!(concat!(env!("CARGO_MANIFEST_DIR"), "/src/template.txt"));
+/// A template that ships with the crate.
+struct Preset {
+    name: &'static str,
+    module: &'static str,
+    grammar: Option<&'static str>,
+}
+
+const PRESETS: &[Preset] = &[
+    Preset {
+        name: "plain",
+        module: include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/plain.txt"
+        )),
+        grammar: None,
+    },
+    Preset {
+        name: "pest",
+        module: include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/pest.txt"
+        )),
+        grammar: Some(include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/pest.pest"
+        ))),
+    },
+    Preset {
+        name: "grid",
+        module: include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/grid.txt"
+        )),
+        grammar: None,
+    },
+    Preset {
+        name: "shared-parse",
+        module: include_str!(concat!(
+            env!("CARGO_MANIFEST_DIR"),
+            "/src/templates/shared-parse.txt"
+        )),
+        grammar: None,
+    },
+];
+
+/// The files of a solution before placeholders are replaced.
+struct Template {
+    module: String,
+    /// A pest grammar, written to `src/parsers/<year>/day<DD>.pest`.
+    grammar: Option<String>,
+}
+
+/// Values of the placeholders in a template, e.g. `%YEAR%`.
+struct Placeholders {
+    id: PuzzleId,
+    title: String,
+    answer_type: String,
+    part_one_example: Option<String>,
+    part_two_example: Option<String>,
+    /// Whether part two has an example of its own in `<day>-2.txt`.
+    has_part_two_input: bool,
+    grammar_path: String,
+}
+
+impl Placeholders {
+    fn render(&self, template: &str) -> String {
+        let example = |answer: &Option<String>| match answer {
+            Some(answer) if fits_answer_type(answer, &self.answer_type) => {
+                format!("Some({answer})")
+            }
+            Some(answer) => format!(
+                "None /* TODO: the example answer {answer} is not a {} */",
+                self.answer_type
+            ),
+            None => "None".into(),
+        };
+
+        let part_two_input = if self.has_part_two_input {
+            "advent_of_code::template::read_file_part(\"examples\", PUZZLE, 2)"
+        } else {
+            "advent_of_code::template::read_file(\"examples\", PUZZLE)"
+        };
+
+        template
+            .replace("%YEAR%", &self.id.year.to_string())
+            .replace("%DAY_NUMBER%", &self.id.day.into_inner().to_string())
+            .replace("%DAY%", &self.id.day.to_string())
+            .replace("%TITLE%", &self.title)
+            .replace("%ANSWER_TYPE%", &self.answer_type)
+            .replace("%PART_ONE_EXAMPLE%", &example(&self.part_one_example))
+            .replace("%PART_TWO_EXAMPLE%", &example(&self.part_two_example))
+            .replace("%PART_TWO_INPUT%", part_two_input)
+            .replace("%GRAMMAR_PATH%", &self.grammar_path)
+    }
+}
+
+/// Whether an example answer is a valid literal of the answer type. Only integer types are
+/// known, answers are not used for other types.
+fn fits_answer_type(answer: &str, answer_type: &str) -> bool {
+    match answer_type {
+        "u8" => answer.parse::<u8>().is_ok(),
+        "u16" => answer.parse::<u16>().is_ok(),
+        "u32" => answer.parse::<u32>().is_ok(),
+        "u64" => answer.parse::<u64>().is_ok(),
+        "u128" => answer.parse::<u128>().is_ok(),
+        "usize" => answer.parse::<usize>().is_ok(),
+        "i8" => answer.parse::<i8>().is_ok(),
+        "i16" => answer.parse::<i16>().is_ok(),
+        "i32" => answer.parse::<i32>().is_ok(),
+        "i64" => answer.parse::<i64>().is_ok(),
+        "i128" => answer.parse::<i128>().is_ok(),
+        "isize" => answer.parse::<isize>().is_ok(),
+        _ => false,
+    }
+}
 
 fn safe_create_file(path: &str) -> Result<File, std::io::Error> {
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
     OpenOptions::new().write(true).create_new(true).open(path)
 }
 
 fn create_file(path: &str) -> Result<File, std::io::Error> {
-    OpenOptions::new().write(true).create(true).open(path)
+    if let Some(parent) = Path::new(path).parent() {
+        fs::create_dir_all(parent)?;
+    }
+
+    OpenOptions::new()
+        .write(true)
+        .create(true)
+        .truncate(false)
+        .open(path)
 }
 
-pub fn handle(day: Day) {
-    let input_path = format!("data/inputs/{day}.txt");
-    let example_path = format!("data/examples/{day}.txt");
-    let module_path = format!("src/bin/{day}.rs");
+/// Loads a template by name. Templates in the configured folder take precedence over the presets.
+fn load_template(name: &str) -> Result<Template, TemplateError> {
+    let folder = &config::get().scaffold.templates;
+    let read = |path: PathBuf| match fs::read_to_string(&path) {
+        Ok(s) => Ok(Some(s)),
+        Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+        Err(source) => Err(TemplateError::Io { path, source }),
+    };
 
-    let mut file = match safe_create_file(&module_path) {
-        Ok(file) => file,
-        Err(e) => {
-            eprintln!("Failed to create module file: {e}");
-            process::exit(1);
-        }
+    if let Some(module) = read(folder.join(format!("{name}.txt")))? {
+        return Ok(Template {
+            module,
+            grammar: read(folder.join(format!("{name}.pest")))?,
+        });
+    }
+
+    let preset = PRESETS
+        .iter()
+        .find(|preset| preset.name == name)
+        .ok_or_else(|| {
+            let names: Vec<_> = PRESETS.iter().map(|p| format!("`{}`", p.name)).collect();
+            TemplateError::Usage(format!(
+                "unknown preset `{name}`, expecting one of {} or the name of a template in \"{}\".",
+                names.join(", "),
+                folder.display()
+            ))
+        })?;
+
+    Ok(Template {
+        module: preset.module.into(),
+        grammar: preset.grammar.map(String::from),
+    })
+}
+
+/// Reads the title from the heading of a downloaded puzzle description, e.g. `Day 6: Wait For It`.
+fn parse_title(markdown: &str) -> Option<String> {
+    markdown.lines().find_map(|line| {
+        let title = line.strip_prefix("## --- ")?.strip_suffix(" ---")?;
+        Some(title.trim().to_string())
+    })
+}
+
+pub fn handle(id: PuzzleId, preset: Option<&str>) -> Result<(), TemplateError> {
+    let config = &config::get().scaffold;
+    let template = load_template(preset.unwrap_or(&config.preset))?;
+
+    let data_path = |folder: &str, extension: &str| {
+        get_data_folder(folder, id.year)
+            .join(format!("{}.{extension}", id.day))
+            .display()
+            .to_string()
     };
 
-    match file.write_all(
-        MODULE_TEMPLATE
-            .replace("%DAY_NUMBER%", &day.into_inner().to_string())
-            .as_bytes(),
-    ) {
-        Ok(()) => {
-            println!("Created module file \"{}\"", &module_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to write module contents: {e}");
-            process::exit(1);
+    let input_path = data_path("inputs", "txt");
+    let example_path = data_path("examples", "txt");
+    let module_path = format!("src/bin/{id}.rs");
+    let grammar_path = format!("parsers/{}/day{}.pest", id.year, id.day);
+
+    let io_error = |path: &str| {
+        let path = PathBuf::from(path);
+        move |source: io::Error| match source.kind() {
+            io::ErrorKind::AlreadyExists => TemplateError::AlreadyExists { path },
+            _ => TemplateError::Io { path, source },
         }
+    };
+
+    let examples = puzzle::read_examples(id);
+    let answer = |part: usize| examples.get(part).and_then(|e| e.answer.clone());
+
+    let placeholders = Placeholders {
+        id,
+        title: fs::read_to_string(data_path("puzzles", "md"))
+            .ok()
+            .and_then(|markdown| parse_title(&markdown))
+            .unwrap_or_else(|| format!("Day {}", id.day.into_inner())),
+        answer_type: config.answer_type.clone(),
+        part_one_example: answer(0),
+        part_two_example: answer(1),
+        has_part_two_input: examples.get(1).is_some_and(|e| e.input.is_some()),
+        grammar_path: grammar_path.clone(),
+    };
+
+    // NOTE: check the grammar up front so that a conflict does not leave a module behind.
+    let grammar_path = format!("src/{grammar_path}");
+    if template.grammar.is_some() && Path::new(&grammar_path).exists() {
+        return Err(TemplateError::AlreadyExists {
+            path: grammar_path.into(),
+        });
     }
 
-    match create_file(&input_path) {
-        Ok(_) => {
-            println!("Created empty input file \"{}\"", &input_path);
-        }
-        Err(e) => {
-            eprintln!("Failed to create input file: {e}");
-            process::exit(1);
+    let mut file = safe_create_file(&module_path).map_err(io_error(&module_path))?;
+    file.write_all(placeholders.render(&template.module).as_bytes())
+        .map_err(io_error(&module_path))?;
+    println!("Created module file \"{}\"", &module_path);
+
+    if let Some(grammar) = &template.grammar {
+        let mut file = safe_create_file(&grammar_path).map_err(io_error(&grammar_path))?;
+        file.write_all(placeholders.render(grammar).as_bytes())
+            .map_err(io_error(&grammar_path))?;
+        println!("Created grammar file \"{}\"", &grammar_path);
+    }
+
+    create_file(&input_path).map_err(io_error(&input_path))?;
+    println!("Created empty input file \"{}\"", &input_path);
+
+    let written = puzzle::write_examples(id, &examples).map_err(io_error(&example_path))?;
+    for path in &written {
+        println!("Created example file \"{}\"", path.display());
+    }
+
+    if !written.contains(&puzzle::get_example_path(id, 1)) {
+        create_file(&example_path).map_err(io_error(&example_path))?;
+        println!("Created empty example file \"{}\"", &example_path);
+    }
+
+    println!("---");
+    println!(
+        "🎄 Type `cargo solve {} --year {}` to run your solution.",
+        id.day, id.year
+    );
+
+    Ok(())
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{load_template, parse_title, Placeholders, PRESETS};
+    use crate::puzzle;
+
+    fn placeholders() -> Placeholders {
+        Placeholders {
+            id: puzzle!(2023, 6),
+            title: "Day 6: Wait For It".into(),
+            answer_type: "u64".into(),
+            part_one_example: Some("288".into()),
+            part_two_example: None,
+            has_part_two_input: false,
+            grammar_path: "parsers/2023/day06.pest".into(),
         }
     }
 
-    match create_file(&example_path) {
-        Ok(_) => {
-            println!("Created empty example file \"{}\"", &example_path);
+    #[test]
+    fn renders_placeholders() {
+        let rendered = placeholders().render(
+            "%TITLE% %YEAR%-%DAY% %DAY_NUMBER% Option<%ANSWER_TYPE%> %PART_ONE_EXAMPLE% %PART_TWO_EXAMPLE% %GRAMMAR_PATH%",
+        );
+
+        assert_eq!(
+            rendered,
+            "Day 6: Wait For It 2023-06 6 Option<u64> Some(288) None parsers/2023/day06.pest"
+        );
+    }
+
+    #[test]
+    fn renders_answers_that_do_not_fit_the_answer_type() {
+        let placeholders = Placeholders {
+            part_one_example: Some("-3".into()),
+            ..placeholders()
+        };
+
+        assert_eq!(
+            placeholders.render("%PART_ONE_EXAMPLE%"),
+            "None /* TODO: the example answer -3 is not a u64 */"
+        );
+    }
+
+    #[test]
+    fn renders_part_two_input() {
+        let shared = placeholders().render("%PART_TWO_INPUT%");
+        assert_eq!(
+            shared,
+            "advent_of_code::template::read_file(\"examples\", PUZZLE)"
+        );
+
+        let own = Placeholders {
+            has_part_two_input: true,
+            ..placeholders()
         }
-        Err(e) => {
-            eprintln!("Failed to create example file: {e}");
-            process::exit(1);
+        .render("%PART_TWO_INPUT%");
+        assert_eq!(
+            own,
+            "advent_of_code::template::read_file_part(\"examples\", PUZZLE, 2)"
+        );
+    }
+
+    #[test]
+    fn renders_every_placeholder_of_the_presets() {
+        for preset in PRESETS {
+            let rendered = placeholders().render(preset.module);
+            assert!(
+                !rendered.contains('%'),
+                "{} has placeholders left",
+                preset.name
+            );
+            assert!(rendered.contains("solution!(year: 2023, 6"));
         }
     }
 
-    println!("---");
-    println!("🎄 Type `cargo solve {day}` to run your solution.");
+    #[test]
+    fn loads_presets() {
+        assert!(load_template("plain").unwrap().grammar.is_none());
+        assert!(load_template("pest").unwrap().grammar.is_some());
+        assert!(load_template("unknown").is_err());
+    }
+
+    #[test]
+    fn parses_titles() {
+        let markdown =
+            "## --- Day 6: Wait For It ---\n\nThe ferry quickly brings you across Island Island.";
+        assert_eq!(parse_title(markdown), Some("Day 6: Wait For It".into()));
+        assert_eq!(parse_title("# readme"), None);
+    }
 }
@@ -1,9 +1,18 @@
 use std::process::{Command, Stdio};
+use std::time::Duration;
 
-use crate::template::Day;
+use crate::template::{error::TemplateError, runner::OutputFormat, PuzzleId};
 
-pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
-    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), day.to_string()];
+pub fn handle(
+    id: PuzzleId,
+    release: bool,
+    dhat: bool,
+    submit_part: Option<u8>,
+    force: bool,
+    format: Option<OutputFormat>,
+    timeout: Option<Duration>,
+) -> Result<(), TemplateError> {
+    let mut cmd_args = vec!["run".to_string(), "--bin".to_string(), id.to_string()];
 
     if dhat {
         cmd_args.extend([
@@ -23,12 +32,48 @@ pub fn handle(day: Day, release: bool, dhat: bool, submit_part: Option<u8>) {
         cmd_args.push(submit_part.to_string());
     }
 
-    let mut cmd = Command::new("cargo")
-        .args(&cmd_args)
+    if force {
+        cmd_args.push("--force".to_string());
+    }
+
+    if let Some(timeout) = timeout {
+        cmd_args.push("--timeout".to_string());
+        cmd_args.push(timeout.as_secs_f64().to_string());
+    }
+
+    // NOTE: the solution falls back to the format in `aoc.toml` if none is passed.
+    if let Some(format) = format {
+        let format = match format {
+            OutputFormat::Text => "text",
+            OutputFormat::Json => "json",
+            OutputFormat::Ndjson => "ndjson",
+        };
+        cmd_args.extend(["--format".to_string(), format.to_string()]);
+    }
+
+    run_cargo(&cmd_args)
+}
+
+/// Runs `cargo` with the given arguments, passing its output through.
+pub fn run_cargo(cmd_args: &[String]) -> Result<(), TemplateError> {
+    let command = format!("cargo {}", cmd_args.join(" "));
+
+    let status = Command::new("cargo")
+        .args(cmd_args)
         .stdout(Stdio::inherit())
         .stderr(Stdio::inherit())
-        .spawn()
-        .unwrap();
+        .status()
+        .map_err(|source| TemplateError::Spawn {
+            command: command.clone(),
+            source,
+        })?;
 
-    cmd.wait().unwrap();
+    if status.success() {
+        Ok(())
+    } else {
+        Err(TemplateError::CommandFailed {
+            command,
+            code: status.code(),
+        })
+    }
 }
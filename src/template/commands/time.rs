@@ -1,40 +1,182 @@
 use std::collections::HashSet;
+use std::env;
 
-use crate::template::run_multi::run_multi;
+pub use crate::template::compare::CompareOptions;
+
+use crate::template::commands::solve::run_cargo;
+use crate::template::compare::{compare, print_deltas, Change};
+use crate::template::config;
+use crate::template::error::TemplateError;
+use crate::template::history::{self, HistoryEntry};
+use crate::template::run_multi::{get_puzzles, run_multi};
+use crate::template::runner::{DayResult, RunOptions, Solution};
 use crate::template::timings::Timings;
-use crate::template::{all_days, readme_benchmarks, Day};
+use crate::template::{readme_benchmarks, PuzzleId, Year};
 
-pub fn handle(day: Option<Day>, run_all: bool, store: bool) {
-    let stored_timings = Timings::read_from_file();
+pub fn handle(
+    solutions: &[Solution],
+    year: Option<Year>,
+    puzzle: Option<PuzzleId>,
+    run_all: bool,
+    store: bool,
+    options: &RunOptions,
+    compare_options: Option<CompareOptions>,
+) -> Result<(), TemplateError> {
+    let stored_timings = Timings::read_from_file()?;
 
-    let days_to_run = day.map_or_else(
+    let puzzles_to_run = puzzle.map_or_else(
         || {
-            if run_all {
-                all_days().collect()
+            let puzzles = get_puzzles(solutions, year);
+            // comparing requires re-running the days that are already benched.
+            if run_all || compare_options.is_some() {
+                puzzles
             } else {
                 // when the `--all` flag is not set, filter out days that are fully benched.
-                all_days()
-                    .filter(|day| !stored_timings.is_day_complete(*day))
+                puzzles
+                    .into_iter()
+                    .filter(|id| !stored_timings.is_day_complete(*id))
                     .collect()
             }
         },
-        |day| HashSet::from([day]),
+        |id| HashSet::from([id]),
     );
 
-    let timings = run_multi(&days_to_run, true, true).unwrap();
+    let options = RunOptions {
+        timed: true,
+        ..options.clone()
+    };
+
+    let results = run_multi(solutions, &puzzles_to_run, &options);
+    let timings = Timings::from(results.as_slice());
+    let is_reliable = warn_on_timeout(&results);
+
+    let mut has_regression = false;
 
-    if store {
+    if let Some(compare_options) = compare_options {
+        let deltas = compare(&stored_timings, &timings, compare_options.threshold);
+
+        if options.is_text() {
+            print_deltas(&deltas);
+        }
+
+        has_regression = compare_options.fail_on_regression
+            && deltas.iter().any(|d| d.change == Change::Regressed);
+    }
+
+    if store && !is_reliable {
+        eprintln!("Not storing benchmarks.");
+    } else if store {
         let merged_timings = stored_timings.merge(&timings);
-        merged_timings.store_file().unwrap();
+        merged_timings.store_file()?;
 
-        println!();
-        match readme_benchmarks::update(merged_timings) {
-            Ok(()) => {
-                println!("Stored updated benchmarks.");
-            }
-            Err(_) => {
-                eprintln!("Failed to store updated benchmarks.");
-            }
+        // NOTE: the speedups compare against the history including this run, even if it could
+        // not be appended to the file.
+        let mut entries = history::read_from_file();
+        let entry = HistoryEntry::now(timings);
+        if let Err(e) = history::append(&entry) {
+            eprintln!("Failed to append benchmarks to history: {e}");
+        }
+        entries.push(entry);
+
+        readme_benchmarks::update(
+            merged_timings,
+            &history::previous_timings(&entries),
+            &config::get().readme,
+        )?;
+        if options.is_text() {
+            println!();
+            println!("Stored updated benchmarks.");
+        }
+    }
+
+    if has_regression {
+        return Err(TemplateError::CheckFailed(
+            "benchmarks regressed beyond the threshold.".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+/// Measures the heap usage of each part with `dhat`. Durations are skewed by the heap profiler,
+/// so each part runs once and only its memory usage is stored.
+pub fn handle_memory(
+    solutions: &[Solution],
+    year: Option<Year>,
+    puzzle: Option<PuzzleId>,
+    store: bool,
+    options: &RunOptions,
+) -> Result<(), TemplateError> {
+    // NOTE: the allocator is only tracked when built with the `dhat-heap` feature.
+    if !cfg!(feature = "dhat-heap") {
+        let mut cmd_args: Vec<String> = [
+            "run",
+            "--quiet",
+            "--profile",
+            "dhat",
+            "--features",
+            "dhat-heap",
+            "--bin",
+            "runner",
+            "--",
+        ]
+        .map(String::from)
+        .into();
+        cmd_args.extend(env::args().skip(1));
+        return run_cargo(&cmd_args);
+    }
+
+    let puzzles_to_run =
+        puzzle.map_or_else(|| get_puzzles(solutions, year), |id| HashSet::from([id]));
+
+    let options = RunOptions {
+        timed: false,
+        ..options.clone()
+    };
+
+    let results = run_multi(solutions, &puzzles_to_run, &options);
+    let is_reliable = warn_on_timeout(&results);
+
+    if store && !is_reliable {
+        eprintln!("Not storing memory usage.");
+    } else if store {
+        let timings = Timings::read_from_file()?.merge_memory(&Timings::from(results.as_slice()));
+        timings.store_file()?;
+        // NOTE: durations are unchanged, so are the runs the speedups compare against.
+        let previous = history::previous_timings(&history::read_from_file());
+        readme_benchmarks::update(timings, &previous, &config::get().readme)?;
+
+        if options.is_text() {
+            println!();
+            println!("Stored memory usage.");
         }
     }
+
+    Ok(())
+}
+
+/// Warns if a solution timed out, as it keeps running in the background and skews the
+/// measurements of the days after it. Returns false in that case.
+fn warn_on_timeout(results: &[DayResult]) -> bool {
+    let timed_out: Vec<String> = results
+        .iter()
+        .filter(|result| result.timed_out)
+        .map(|result| result.id.to_string())
+        .collect();
+
+    if timed_out.is_empty() {
+        return true;
+    }
+
+    eprintln!();
+    eprintln!(
+        "Warning: {} timed out and kept running in the background, the results are unreliable.",
+        timed_out.join(", ")
+    );
+    false
+}
+
+/// Prints the benchmark history of a day.
+pub fn handle_history(id: PuzzleId) {
+    history::print_day_history(&history::read_from_file(), id);
 }
@@ -0,0 +1,279 @@
+use std::collections::{BTreeSet, HashMap, HashSet};
+
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::error::TemplateError;
+use crate::template::run_multi::{get_puzzles, run_days, run_multi};
+use crate::template::runner::{record, OutputFormat, RunOptions, Solution};
+use crate::template::{PuzzleId, Year, ANSI_BOLD, ANSI_RESET};
+
+/// Outcome of comparing a part's result to the recorded answer.
+#[derive(Debug, PartialEq, Eq)]
+enum Verdict {
+    Pass,
+    Fail {
+        expected: String,
+        actual: String,
+    },
+    NoResult {
+        expected: String,
+    },
+    Missing,
+    /// The part has a recorded answer, but the day has not been scaffolded.
+    Unscaffolded {
+        expected: String,
+    },
+    /// The day could not be run.
+    Error(String),
+}
+
+impl Verdict {
+    fn as_str(&self) -> &'static str {
+        match self {
+            Verdict::Pass => "pass",
+            Verdict::Fail { .. } => "fail",
+            Verdict::NoResult { .. } => "no_result",
+            Verdict::Missing => "missing",
+            Verdict::Unscaffolded { .. } => "unscaffolded",
+            Verdict::Error(_) => "error",
+        }
+    }
+}
+
+/// The verdict of a single part, or of a whole day if it could not be run.
+struct Check {
+    id: PuzzleId,
+    part: Option<u8>,
+    verdict: Verdict,
+}
+
+impl From<&Check> for JsonValue {
+    fn from(check: &Check) -> Self {
+        let string =
+            |s: Option<&String>| s.map_or(JsonValue::Null, |s| JsonValue::String(s.clone()));
+
+        let (expected, actual, error) = match &check.verdict {
+            Verdict::Fail { expected, actual } => (Some(expected), Some(actual), None),
+            Verdict::NoResult { expected } | Verdict::Unscaffolded { expected } => {
+                (Some(expected), None, None)
+            }
+            Verdict::Error(error) => (None, None, Some(error)),
+            Verdict::Pass | Verdict::Missing => (None, None, None),
+        };
+
+        let mut map = record("verify", check.id);
+        map.insert(
+            "part".into(),
+            check
+                .part
+                .map_or(JsonValue::Null, |part| JsonValue::Number(f64::from(part))),
+        );
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(check.verdict.as_str().into()),
+        );
+        map.insert("expected".into(), string(expected));
+        map.insert("actual".into(), string(actual));
+        map.insert("error".into(), string(error));
+        JsonValue::Object(map)
+    }
+}
+
+pub fn handle(
+    solutions: &[Solution],
+    year: Option<Year>,
+    puzzle: Option<PuzzleId>,
+    options: &RunOptions,
+) -> Result<(), TemplateError> {
+    let answers = Answers::read_from_file()?;
+
+    let puzzles_to_run =
+        puzzle.map_or_else(|| get_puzzles(solutions, year), |id| HashSet::from([id]));
+
+    // NOTE: the JSON document lists the results next to the checks, instead of printing them first.
+    let results = match options.format {
+        OutputFormat::Json => run_days(solutions, &puzzles_to_run, options),
+        _ => run_multi(solutions, &puzzles_to_run, options),
+    };
+
+    let mut checks = Vec::new();
+    let mut failed = false;
+
+    for id in puzzles_to_run.iter().collect::<BTreeSet<_>>() {
+        let result = results.iter().find(|r| r.id == *id);
+
+        if let Some(error) = result.and_then(|r| r.error.as_ref()) {
+            // only days with recorded answers are expected to be runnable.
+            failed |= (1..=2).any(|part| answers.get(*id, part).is_some());
+            checks.push(Check {
+                id: *id,
+                part: None,
+                verdict: Verdict::Error(error.clone()),
+            });
+            continue;
+        }
+
+        for part in 1..=2 {
+            let expected = answers.get(*id, part);
+
+            let verdict = match result {
+                Some(result) => {
+                    let actual = result
+                        .parts
+                        .iter()
+                        .find(|p| p.part == part)
+                        .and_then(|p| p.answer.as_deref());
+                    verify(expected, actual)
+                }
+                None => match expected {
+                    Some(expected) => Verdict::Unscaffolded {
+                        expected: expected.into(),
+                    },
+                    // NOTE: days without solutions or answers are not worth reporting.
+                    None => continue,
+                },
+            };
+
+            failed |= matches!(
+                verdict,
+                Verdict::Fail { .. } | Verdict::NoResult { .. } | Verdict::Unscaffolded { .. }
+            );
+            checks.push(Check {
+                id: *id,
+                part: Some(part),
+                verdict,
+            });
+        }
+    }
+
+    match options.format {
+        OutputFormat::Text => print_checks(&checks),
+        OutputFormat::Ndjson => {
+            for check in &checks {
+                println!("{}", JsonValue::from(check).stringify().unwrap());
+            }
+        }
+        OutputFormat::Json => {
+            let days = JsonValue::Array(results.iter().map(JsonValue::from).collect());
+            let checks = checks
+                .iter()
+                .map(|check| {
+                    let mut value = JsonValue::from(check);
+                    if let JsonValue::Object(map) = &mut value {
+                        map.remove("type");
+                    }
+                    value
+                })
+                .collect();
+
+            let document: HashMap<String, JsonValue> = [
+                ("days".to_string(), days),
+                ("verification".to_string(), JsonValue::Array(checks)),
+            ]
+            .into();
+            println!("{}", JsonValue::Object(document).format().unwrap());
+        }
+    }
+
+    if failed {
+        return Err(TemplateError::CheckFailed(
+            "some answers do not match the recorded ones.".into(),
+        ));
+    }
+
+    Ok(())
+}
+
+fn print_checks(checks: &[Check]) {
+    println!();
+    println!("{ANSI_BOLD}Verification{ANSI_RESET}");
+    println!("------");
+
+    for check in checks {
+        let status = match &check.verdict {
+            Verdict::Pass => "✔ pass".to_string(),
+            Verdict::Fail { expected, actual } => {
+                format!("✖ fail (expected {expected}, got {actual})")
+            }
+            Verdict::NoResult { expected } => {
+                format!("✖ fail (expected {expected}, got no result)")
+            }
+            Verdict::Missing => "? missing".to_string(),
+            Verdict::Unscaffolded { expected } => {
+                format!("✖ fail (expected {expected}, but the day is not scaffolded)")
+            }
+            Verdict::Error(error) => format!("✖ {error}"),
+        };
+
+        match check.part {
+            Some(part) => println!(
+                "{} Day {} Part {part}: {status}",
+                check.id.year, check.id.day
+            ),
+            None => println!("{} Day {}: {status}", check.id.year, check.id.day),
+        }
+    }
+}
+
+fn verify(expected: Option<&str>, actual: Option<&str>) -> Verdict {
+    match (expected, actual) {
+        (None, _) => Verdict::Missing,
+        (Some(expected), None) => Verdict::NoResult {
+            expected: expected.into(),
+        },
+        (Some(expected), Some(actual)) if expected == actual => Verdict::Pass,
+        (Some(expected), Some(actual)) => Verdict::Fail {
+            expected: expected.into(),
+            actual: actual.into(),
+        },
+    }
+}
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::collections::HashMap;
+
+    use tinyjson::JsonValue;
+
+    use super::{verify, Check, Verdict};
+    use crate::puzzle;
+
+    #[test]
+    fn verifies_answers() {
+        assert_eq!(verify(Some("42"), Some("42")), Verdict::Pass);
+        assert_eq!(verify(None, Some("42")), Verdict::Missing);
+        assert_eq!(verify(None, None), Verdict::Missing);
+        assert_eq!(
+            verify(Some("42"), Some("41")),
+            Verdict::Fail {
+                expected: "42".into(),
+                actual: "41".into()
+            }
+        );
+        assert_eq!(
+            verify(Some("42"), None),
+            Verdict::NoResult {
+                expected: "42".into()
+            }
+        );
+    }
+
+    #[test]
+    fn serializes_unscaffolded_checks() {
+        let check = Check {
+            id: puzzle!(2023, 12),
+            part: Some(1),
+            verdict: Verdict::Unscaffolded {
+                expected: "21".into(),
+            },
+        };
+
+        let value = JsonValue::from(&check);
+        let map = value.get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(map["verdict"], JsonValue::String("unscaffolded".into()));
+        assert_eq!(map["expected"], JsonValue::String("21".into()));
+        assert_eq!(map["actual"], JsonValue::Null);
+        assert_eq!(map["part"], JsonValue::Number(1.0));
+    }
+}
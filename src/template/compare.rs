@@ -0,0 +1,193 @@
+/// Compares a fresh benchmark run against previously stored timings.
+use crate::template::timings::{Timing, Timings};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_GREEN, ANSI_RED, ANSI_RESET};
+
+/// Options for `cargo time --compare`.
+#[derive(Clone, Copy, Debug)]
+pub struct CompareOptions {
+    /// Relative change in percent below which a part is considered unchanged.
+    pub threshold: f64,
+    /// Exit with a non-zero status if any part regressed beyond the threshold.
+    pub fail_on_regression: bool,
+}
+
+impl Default for CompareOptions {
+    fn default() -> Self {
+        Self {
+            threshold: 5.0,
+            fail_on_regression: false,
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Change {
+    Improved,
+    Regressed,
+    Unchanged,
+}
+
+/// The change in execution time of a single step (parse, part 1 or part 2) of a day.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Delta {
+    pub id: PuzzleId,
+    pub step: &'static str,
+    pub old_nanos: f64,
+    pub new_nanos: f64,
+    pub change: Change,
+}
+
+impl Delta {
+    pub fn delta_nanos(&self) -> f64 {
+        self.new_nanos - self.old_nanos
+    }
+
+    pub fn delta_percent(&self) -> f64 {
+        if self.old_nanos == 0.0 {
+            0.0
+        } else {
+            self.delta_nanos() / self.old_nanos * 100.0
+        }
+    }
+}
+
+/// Computes the deltas for all steps present in both the stored and the new timings.
+pub fn compare(stored: &Timings, new: &Timings, threshold: f64) -> Vec<Delta> {
+    let mut deltas = vec![];
+
+    for new_timing in &new.data {
+        let Some(old_timing) = stored.data.iter().find(|t| t.id == new_timing.id) else {
+            continue;
+        };
+
+        for (step, nanos) in [
+            ("Parse", Timing::parse_nanos as fn(&Timing) -> Option<f64>),
+            ("Part 1", Timing::part_1_nanos),
+            ("Part 2", Timing::part_2_nanos),
+        ] {
+            let (Some(old_nanos), Some(new_nanos)) = (nanos(old_timing), nanos(new_timing)) else {
+                continue;
+            };
+
+            let mut delta = Delta {
+                id: new_timing.id,
+                step,
+                old_nanos,
+                new_nanos,
+                change: Change::Unchanged,
+            };
+
+            let percent = delta.delta_percent();
+            if percent > threshold {
+                delta.change = Change::Regressed;
+            } else if percent < -threshold {
+                delta.change = Change::Improved;
+            }
+
+            deltas.push(delta);
+        }
+    }
+
+    deltas
+}
+
+pub fn print_deltas(deltas: &[Delta]) {
+    println!();
+    println!("{ANSI_BOLD}Comparison{ANSI_RESET}");
+    println!("------");
+
+    if deltas.is_empty() {
+        println!("No stored timings to compare against.");
+        return;
+    }
+
+    for delta in deltas {
+        let marker = match delta.change {
+            Change::Improved => format!("{ANSI_GREEN}▼ improved{ANSI_RESET}"),
+            Change::Regressed => format!("{ANSI_RED}▲ regressed{ANSI_RESET}"),
+            Change::Unchanged => "= unchanged".into(),
+        };
+
+        println!(
+            "{} Day {} {}: {} → {} ({}{}, {:+.1}%) {marker}",
+            delta.id.year,
+            delta.id.day,
+            delta.step,
+            format_nanos(delta.old_nanos),
+            format_nanos(delta.new_nanos),
+            if delta.delta_nanos() < 0.0 { "-" } else { "+" },
+            format_nanos(delta.delta_nanos().abs()),
+            delta.delta_percent(),
+        );
+    }
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn format_nanos(nanos: f64) -> String {
+    format!(
+        "{:.1?}",
+        std::time::Duration::from_nanos(nanos.round() as u64)
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{compare, Change};
+    use crate::{
+        puzzle,
+        template::{
+            timings::{Timing, Timings},
+            PuzzleId,
+        },
+    };
+
+    fn timing(id: PuzzleId, part_1: &str, part_2: Option<&str>) -> Timing {
+        Timing {
+            id,
+            parse: None,
+            part_1: Some(part_1.into()),
+            part_2: part_2.map(Into::into),
+            parse_stats: None,
+            part_1_stats: None,
+            part_2_stats: None,
+            part_1_memory: None,
+            part_2_memory: None,
+            stale_parts: vec![],
+            total_nanos: 0.0,
+        }
+    }
+
+    #[test]
+    fn classifies_changes() {
+        let stored = Timings {
+            data: vec![
+                timing(puzzle!(2023, 1), "10.0ms", Some("1.0ms")),
+                timing(puzzle!(2023, 2), "100.0µs", Some("1.0s")),
+            ],
+        };
+        let new = Timings {
+            data: vec![
+                timing(puzzle!(2023, 1), "12.0ms", Some("900.0µs")),
+                timing(puzzle!(2023, 2), "102.0µs", None),
+                timing(puzzle!(2023, 3), "1.0ms", None),
+            ],
+        };
+
+        let deltas = compare(&stored, &new, 5.0);
+        assert_eq!(deltas.len(), 3);
+
+        assert_eq!(deltas[0].id, puzzle!(2023, 1));
+        assert_eq!(deltas[0].step, "Part 1");
+        assert_eq!(deltas[0].change, Change::Regressed);
+        assert_eq!(deltas[0].delta_nanos(), 2_000_000.0);
+        assert_eq!(deltas[0].delta_percent(), 20.0);
+
+        assert_eq!(deltas[1].step, "Part 2");
+        assert_eq!(deltas[1].change, Change::Improved);
+
+        assert_eq!(deltas[2].id, puzzle!(2023, 2));
+        assert_eq!(deltas[2].change, Change::Unchanged);
+    }
+}
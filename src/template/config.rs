@@ -0,0 +1,543 @@
+/// Project configuration, read from `aoc.toml` in the project root.
+/// Every key is optional, a missing file behaves like an empty one.
+/// Command-line flags, e.g. `--year` or `--timeout`, take precedence over the configured values.
+use std::{
+    fmt::Display, fs, io::ErrorKind, path::PathBuf, str::FromStr, sync::OnceLock, time::Duration,
+};
+use toml::{Table, Value};
+
+use crate::template::{
+    error::TemplateError,
+    readme_benchmarks,
+    runner::{OutputFormat, DEFAULT_BENCH_BUDGET, DEFAULT_TIMEOUT},
+    Year,
+};
+
+static CONFIG_FILE_PATH: &str = "./aoc.toml";
+
+static CONFIG: OnceLock<Config> = OnceLock::new();
+
+/// Returns the project configuration, reading it on first use. Exits if the file is malformed.
+pub fn get() -> &'static Config {
+    CONFIG.get_or_init(|| Config::read_from_file().unwrap_or_else(|err| err.exit()))
+}
+
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct Config {
+    /// Year of commands that are not passed `--year`, unless `AOC_YEAR` is set.
+    pub year: Option<Year>,
+    pub paths: PathsConfig,
+    pub run: RunConfig,
+    pub bench: BenchConfig,
+    pub scaffold: ScaffoldConfig,
+    pub readme: ReadmeConfig,
+}
+
+impl Config {
+    /// Reads the configuration file. If not present, returns the defaults.
+    pub fn read_from_file() -> Result<Self, TemplateError> {
+        match fs::read_to_string(CONFIG_FILE_PATH) {
+            Ok(s) => Config::from_str(&s).map_err(|reason| TemplateError::MalformedData {
+                path: CONFIG_FILE_PATH.into(),
+                reason,
+            }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Config::default()),
+            Err(source) => Err(TemplateError::Io {
+                path: CONFIG_FILE_PATH.into(),
+                source,
+            }),
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct PathsConfig {
+    /// Folder of the inputs, examples and puzzles of each year, and of the stored timings and answers.
+    pub data: PathBuf,
+}
+
+impl Default for PathsConfig {
+    fn default() -> Self {
+        Self {
+            data: PathBuf::from("data"),
+        }
+    }
+}
+
+impl PathsConfig {
+    /// Path of a file in the data folder, e.g. `data/timings.json`.
+    pub fn data_file(&self, name: &str) -> PathBuf {
+        self.data.join(name)
+    }
+}
+
+/// Defaults of the options that control how solutions are run.
+#[derive(Clone, Debug, PartialEq)]
+pub struct RunConfig {
+    pub format: OutputFormat,
+    /// Time limit of a single part for solutions that do not set their own.
+    pub timeout: Duration,
+}
+
+impl Default for RunConfig {
+    fn default() -> Self {
+        Self {
+            format: OutputFormat::default(),
+            timeout: DEFAULT_TIMEOUT,
+        }
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchConfig {
+    /// Total time spent benching a single part.
+    pub budget: Duration,
+    /// Bounds of the number of samples taken of a single part. Slow parts may take fewer
+    /// samples than the minimum if the budget runs out.
+    pub min_samples: u128,
+    pub max_samples: u128,
+}
+
+impl Default for BenchConfig {
+    fn default() -> Self {
+        Self {
+            budget: DEFAULT_BENCH_BUDGET,
+            min_samples: 10,
+            max_samples: 10000,
+        }
+    }
+}
+
+/// Controls the files created by `cargo scaffold`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ScaffoldConfig {
+    /// Template used if `--preset` is not passed.
+    pub preset: String,
+    /// Folder of user-defined templates, which take precedence over the built-in presets.
+    pub templates: PathBuf,
+    /// Return type of the parts, wrapped in an [`Option`].
+    pub answer_type: String,
+}
+
+impl Default for ScaffoldConfig {
+    fn default() -> Self {
+        Self {
+            preset: "plain".into(),
+            templates: PathBuf::from("templates"),
+            answer_type: "u32".into(),
+        }
+    }
+}
+
+/// Controls how `cargo time --store` renders the benchmark table in the readme.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ReadmeConfig {
+    /// File that contains the benchmark table.
+    pub path: PathBuf,
+    /// Comment that marks the start and end of the benchmark table.
+    pub marker: String,
+    /// Heading above the table.
+    pub title: String,
+    /// Columns after the day. If not set, the parse timing if recorded, the part timings and, if
+    /// recorded, the peak memory.
+    pub columns: Option<Vec<Column>>,
+    pub sort: SortOrder,
+    /// Appends a column that visualizes the total duration of each day relative to the slowest one.
+    pub bars: Option<BarStyle>,
+    /// Width of the longest bar in characters.
+    pub bar_width: usize,
+    /// Line below the table, `{total}` is replaced with the total duration. Empty to omit it.
+    pub footer: String,
+}
+
+impl Default for ReadmeConfig {
+    fn default() -> Self {
+        Self {
+            path: PathBuf::from(readme_benchmarks::README_PATH),
+            marker: readme_benchmarks::MARKER.into(),
+            title: "## Benchmarks".into(),
+            columns: None,
+            sort: SortOrder::Day,
+            bars: None,
+            bar_width: 20,
+            footer: "**Total: {total}**".into(),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Column {
+    Parse,
+    Part1,
+    Part2,
+    /// Number of benchmark samples taken for each part.
+    Samples,
+    /// Peak heap usage, recorded with `cargo time --memory`.
+    Memory,
+    /// Total duration of the previous benchmark run of a day relative to the current one.
+    Speedup,
+}
+
+impl FromStr for Column {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "parse" => Ok(Self::Parse),
+            "part_1" => Ok(Self::Part1),
+            "part_2" => Ok(Self::Part2),
+            "samples" => Ok(Self::Samples),
+            "memory" => Ok(Self::Memory),
+            "speedup" => Ok(Self::Speedup),
+            x => Err(format!(
+                "unknown column `{x}`, expecting one of `parse`, `part_1`, `part_2`, `samples`, `memory` or `speedup`"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum SortOrder {
+    #[default]
+    Day,
+    /// Slowest day first.
+    Duration,
+}
+
+impl FromStr for SortOrder {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "day" => Ok(Self::Day),
+            "duration" => Ok(Self::Duration),
+            x => Err(format!(
+                "unknown sort order `{x}`, expecting one of `day` or `duration`"
+            )),
+        }
+    }
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum BarStyle {
+    Ascii,
+    Unicode,
+}
+
+impl FromStr for BarStyle {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ascii" => Ok(Self::Ascii),
+            "unicode" => Ok(Self::Unicode),
+            x => Err(format!(
+                "unknown bar style `{x}`, expecting one of `ascii` or `unicode`"
+            )),
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl FromStr for Config {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let table = s.parse::<Table>().map_err(|e| e.to_string())?;
+        let mut config = Config::default();
+
+        for (key, value) in &table {
+            match key.as_str() {
+                "year" => {
+                    config.year = Some(
+                        value
+                            .as_integer()
+                            .and_then(|x| u16::try_from(x).ok())
+                            .and_then(Year::new)
+                            .ok_or("Expected year to be a year of advent.")?,
+                    );
+                }
+                "paths" => config.paths = PathsConfig::try_from(get_table(value, "paths")?)?,
+                "run" => config.run = RunConfig::try_from(get_table(value, "run")?)?,
+                "bench" => config.bench = BenchConfig::try_from(get_table(value, "bench")?)?,
+                "scaffold" => {
+                    config.scaffold = ScaffoldConfig::try_from(get_table(value, "scaffold")?)?;
+                }
+                "readme" => config.readme = ReadmeConfig::try_from(get_table(value, "readme")?)?,
+                x => return Err(format!("Unknown key `{x}`.")),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl TryFrom<&Table> for PathsConfig {
+    type Error = String;
+
+    fn try_from(table: &Table) -> Result<Self, Self::Error> {
+        let mut config = PathsConfig::default();
+
+        for (key, value) in table {
+            match key.as_str() {
+                "data" => config.data = get_str(value, "paths.data")?.into(),
+                x => return Err(format!("Unknown key `paths.{x}`.")),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl TryFrom<&Table> for RunConfig {
+    type Error = String;
+
+    fn try_from(table: &Table) -> Result<Self, Self::Error> {
+        let mut config = RunConfig::default();
+
+        for (key, value) in table {
+            match key.as_str() {
+                "format" => config.format = parse(value, "run.format")?,
+                "timeout" => config.timeout = get_seconds(value, "run.timeout")?,
+                x => return Err(format!("Unknown key `run.{x}`.")),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl TryFrom<&Table> for BenchConfig {
+    type Error = String;
+
+    fn try_from(table: &Table) -> Result<Self, Self::Error> {
+        let mut config = BenchConfig::default();
+
+        for (key, value) in table {
+            match key.as_str() {
+                "budget" => config.budget = get_seconds(value, "bench.budget")?,
+                "min_samples" => config.min_samples = get_count(value, "bench.min_samples")?,
+                "max_samples" => config.max_samples = get_count(value, "bench.max_samples")?,
+                x => return Err(format!("Unknown key `bench.{x}`.")),
+            }
+        }
+
+        if config.min_samples > config.max_samples {
+            return Err("Expected bench.min_samples to be at most bench.max_samples.".into());
+        }
+
+        Ok(config)
+    }
+}
+
+impl TryFrom<&Table> for ScaffoldConfig {
+    type Error = String;
+
+    fn try_from(table: &Table) -> Result<Self, Self::Error> {
+        let mut config = ScaffoldConfig::default();
+
+        for (key, value) in table {
+            match key.as_str() {
+                "preset" => config.preset = get_str(value, "scaffold.preset")?.into(),
+                "templates" => config.templates = get_str(value, "scaffold.templates")?.into(),
+                "answer_type" => {
+                    config.answer_type = get_str(value, "scaffold.answer_type")?.into();
+                }
+                x => return Err(format!("Unknown key `scaffold.{x}`.")),
+            }
+        }
+
+        Ok(config)
+    }
+}
+
+impl TryFrom<&Table> for ReadmeConfig {
+    type Error = String;
+
+    fn try_from(table: &Table) -> Result<Self, Self::Error> {
+        let mut config = ReadmeConfig::default();
+
+        for (key, value) in table {
+            match key.as_str() {
+                "path" => config.path = get_str(value, "readme.path")?.into(),
+                "marker" => config.marker = get_str(value, "readme.marker")?.into(),
+                "title" => config.title = get_str(value, "readme.title")?.into(),
+                "columns" => {
+                    let columns = value
+                        .as_array()
+                        .ok_or("Expected readme.columns to be an array.")?
+                        .iter()
+                        .map(|column| parse(column, "readme.columns"))
+                        .collect::<Result<_, _>>()?;
+                    config.columns = Some(columns);
+                }
+                "sort" => config.sort = parse(value, "readme.sort")?,
+                "bars" => config.bars = Some(parse(value, "readme.bars")?),
+                "bar_width" => config.bar_width = get_count(value, "readme.bar_width")?,
+                "footer" => config.footer = get_str(value, "readme.footer")?.into(),
+                x => return Err(format!("Unknown key `readme.{x}`.")),
+            }
+        }
+
+        if config.marker.is_empty() {
+            return Err("Expected readme.marker to not be empty.".into());
+        }
+
+        Ok(config)
+    }
+}
+
+fn get_table<'a>(value: &'a Value, key: &str) -> Result<&'a Table, String> {
+    value
+        .as_table()
+        .ok_or_else(|| format!("Expected {key} to be a table."))
+}
+
+fn get_str<'a>(value: &'a Value, key: &str) -> Result<&'a str, String> {
+    value
+        .as_str()
+        .ok_or_else(|| format!("Expected {key} to be a string."))
+}
+
+/// Reads a positive integer.
+fn get_count<T: TryFrom<i64>>(value: &Value, key: &str) -> Result<T, String> {
+    value
+        .as_integer()
+        .filter(|x| *x > 0)
+        .and_then(|x| T::try_from(x).ok())
+        .ok_or_else(|| format!("Expected {key} to be a positive integer."))
+}
+
+/// Reads a positive number of seconds, either an integer or a float.
+#[allow(clippy::cast_precision_loss)]
+fn get_seconds(value: &Value, key: &str) -> Result<Duration, String> {
+    value
+        .as_float()
+        .or(value.as_integer().map(|x| x as f64))
+        .filter(|x| *x > 0.0)
+        .and_then(|x| Duration::try_from_secs_f64(x).ok())
+        .ok_or_else(|| format!("Expected {key} to be a positive number of seconds."))
+}
+
+fn parse<T: FromStr<Err = impl Display>>(value: &Value, key: &str) -> Result<T, String> {
+    get_str(value, key)?
+        .parse()
+        .map_err(|e| format!("Invalid {key}: {e}."))
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{BarStyle, BenchConfig, Column, Config, ReadmeConfig, RunConfig, SortOrder};
+    use crate::{template::runner::OutputFormat, year};
+    use std::{path::PathBuf, str::FromStr, time::Duration};
+
+    #[test]
+    fn defaults_empty_config() {
+        assert_eq!(Config::from_str("").unwrap(), Config::default());
+    }
+
+    #[test]
+    fn parses_readme_config() {
+        let config = Config::from_str(
+            r####"
+            [readme]
+            title = "**Timings**"
+            columns = ["parse", "part_1", "part_2", "speedup"]
+            sort = "duration"
+            bars = "unicode"
+            bar_width = 10
+            footer = ""
+            "####,
+        )
+        .unwrap();
+
+        assert_eq!(
+            config.readme,
+            ReadmeConfig {
+                title: "**Timings**".into(),
+                columns: Some(vec![
+                    Column::Parse,
+                    Column::Part1,
+                    Column::Part2,
+                    Column::Speedup
+                ]),
+                sort: SortOrder::Duration,
+                bars: Some(BarStyle::Unicode),
+                bar_width: 10,
+                footer: String::new(),
+                ..ReadmeConfig::default()
+            }
+        );
+    }
+
+    #[test]
+    fn parses_config() {
+        let config = Config::from_str(
+            r#"
+            year = 2022
+
+            [paths]
+            data = "../aoc-data"
+
+            [run]
+            format = "ndjson"
+            timeout = 120
+
+            [bench]
+            budget = 0.5
+            max_samples = 100
+
+            [scaffold]
+            preset = "grid"
+            answer_type = "u64"
+
+            [readme]
+            path = "docs/benchmarks.md"
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(config.year, Some(year!(2022)));
+        assert_eq!(config.paths.data, PathBuf::from("../aoc-data"));
+        assert_eq!(
+            config.paths.data_file("timings.json"),
+            PathBuf::from("../aoc-data/timings.json")
+        );
+        assert_eq!(
+            config.run,
+            RunConfig {
+                format: OutputFormat::Ndjson,
+                timeout: Duration::from_secs(120),
+            }
+        );
+        assert_eq!(
+            config.bench,
+            BenchConfig {
+                budget: Duration::from_millis(500),
+                min_samples: 10,
+                max_samples: 100,
+            }
+        );
+        assert_eq!(config.scaffold.preset, "grid");
+        assert_eq!(config.scaffold.templates, PathBuf::from("templates"));
+        assert_eq!(config.scaffold.answer_type, "u64");
+        assert_eq!(config.readme.path, PathBuf::from("docs/benchmarks.md"));
+        assert_eq!(config.readme.marker, ReadmeConfig::default().marker);
+    }
+
+    #[test]
+    fn rejects_invalid_config() {
+        assert!(Config::from_str("[readme]\ncolumns = [\"foo\"]").is_err());
+        assert!(Config::from_str("[readme]\nbar_width = 0").is_err());
+        assert!(Config::from_str("[readme]\nsorting = \"day\"").is_err());
+        assert!(Config::from_str("readme = 1").is_err());
+        assert!(Config::from_str("[readme").is_err());
+        assert!(Config::from_str("year = 2014").is_err());
+        assert!(Config::from_str("[run]\ntimeout = -1").is_err());
+        assert!(Config::from_str("[run]\nformat = \"yaml\"").is_err());
+        assert!(Config::from_str("[bench]\nmin_samples = 20\nmax_samples = 10").is_err());
+    }
+}
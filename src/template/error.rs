@@ -0,0 +1,174 @@
+//! Errors of the template layer, with an exit code per category and a hint on how to resolve them.
+
+use std::{fmt::Display, io, path::PathBuf, process};
+
+use crate::template::aoc_client::AocClientError;
+use crate::template::PuzzleId;
+
+#[derive(Debug)]
+pub enum TemplateError {
+    /// The command-line arguments could not be parsed.
+    Usage(String),
+    /// The input file of a puzzle could not be read.
+    MissingInput {
+        id: PuzzleId,
+        path: PathBuf,
+        source: io::Error,
+    },
+    /// The input file of a puzzle exists, but is empty.
+    EmptyInput { id: PuzzleId, path: PathBuf },
+    /// A file managed by the template, e.g. `data/timings.json`, could not be parsed.
+    MalformedData { path: PathBuf, reason: String },
+    /// A file could not be read or written.
+    Io { path: PathBuf, source: io::Error },
+    /// A file that would be overwritten already exists.
+    AlreadyExists { path: PathBuf },
+    /// A `cargo` command could not be spawned.
+    Spawn { command: String, source: io::Error },
+    /// A spawned command exited with a non-zero status.
+    CommandFailed { command: String, code: Option<i32> },
+    /// A solution panicked or timed out before all of its parts ran.
+    SolutionFailed(String),
+    /// A request to adventofcode.com failed.
+    Client(AocClientError),
+    /// A check, e.g. `cargo verify` or `cargo time --fail-on-regression`, did not pass.
+    CheckFailed(String),
+}
+
+impl TemplateError {
+    /// The exit code of the process, following the conventions of `sysexits.h`.
+    pub fn exit_code(&self) -> i32 {
+        match self {
+            TemplateError::CheckFailed(_) => 1,
+            TemplateError::Usage(_) => 64,
+            TemplateError::MalformedData { .. } => 65,
+            TemplateError::MissingInput { .. } | TemplateError::EmptyInput { .. } => 66,
+            TemplateError::SolutionFailed(_) => 70,
+            TemplateError::Client(AocClientError::Io(_)) => 74,
+            TemplateError::Client(_) => 69,
+            TemplateError::Spawn { .. } => 71,
+            TemplateError::AlreadyExists { .. } => 73,
+            TemplateError::Io { .. } => 74,
+            TemplateError::CommandFailed { code, .. } => code.unwrap_or(70),
+        }
+    }
+
+    /// A suggestion on how to resolve the error, if there is one.
+    pub fn hint(&self) -> Option<String> {
+        match self {
+            TemplateError::MissingInput { id, .. } | TemplateError::EmptyInput { id, .. } => {
+                Some(format!(
+                    "run `cargo download {} --year {}` or paste your puzzle input into the file.",
+                    id.day, id.year
+                ))
+            }
+            TemplateError::MalformedData { path, .. } => Some(format!(
+                "fix or delete \"{}\" to start over.",
+                path.display()
+            )),
+            TemplateError::AlreadyExists { .. } => {
+                Some("delete the file first if you want to start over.".into())
+            }
+            TemplateError::Spawn { .. } => Some("make sure `cargo` is on your PATH.".into()),
+            TemplateError::Client(AocClientError::SessionNotFound) => {
+                Some("copy the `session` cookie from adventofcode.com after logging in.".into())
+            }
+            TemplateError::Client(AocClientError::BadStatus(300..=400, _)) => {
+                Some("your session cookie may have expired, try replacing it.".into())
+            }
+            _ => None,
+        }
+    }
+
+    /// Prints the error and its hint to stderr and exits with the error's exit code.
+    pub fn exit(&self) -> ! {
+        eprintln!("Error: {self}");
+        if let Some(hint) = self.hint() {
+            eprintln!("Hint: {hint}");
+        }
+        process::exit(self.exit_code());
+    }
+}
+
+impl Display for TemplateError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            TemplateError::Usage(e) => write!(f, "{e}"),
+            TemplateError::MissingInput { path, source, .. } => write!(
+                f,
+                "could not read input file \"{}\": {source}",
+                path.display()
+            ),
+            TemplateError::EmptyInput { path, .. } => {
+                write!(f, "input file \"{}\" is empty.", path.display())
+            }
+            TemplateError::MalformedData { path, reason } => {
+                write!(f, "could not parse \"{}\": {reason}", path.display())
+            }
+            TemplateError::Io { path, source } => {
+                write!(f, "could not access \"{}\": {source}", path.display())
+            }
+            TemplateError::AlreadyExists { path } => {
+                write!(f, "\"{}\" already exists.", path.display())
+            }
+            TemplateError::Spawn { command, source } => {
+                write!(f, "could not run `{command}`: {source}")
+            }
+            TemplateError::CommandFailed { command, code } => match code {
+                Some(code) => write!(f, "`{command}` exited with status {code}."),
+                None => write!(f, "`{command}` was terminated by a signal."),
+            },
+            TemplateError::SolutionFailed(e) => write!(f, "solution failed: {e}"),
+            TemplateError::Client(e) => write!(f, "{e}"),
+            TemplateError::CheckFailed(e) => write!(f, "{e}"),
+        }
+    }
+}
+
+impl std::error::Error for TemplateError {}
+
+impl From<AocClientError> for TemplateError {
+    fn from(e: AocClientError) -> Self {
+        TemplateError::Client(e)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::TemplateError;
+    use crate::{puzzle, template::aoc_client::AocClientError};
+    use std::path::PathBuf;
+
+    #[test]
+    fn maps_categories_to_exit_codes() {
+        let missing_input = TemplateError::EmptyInput {
+            id: puzzle!(2023, 5),
+            path: PathBuf::from("data/2023/inputs/05.txt"),
+        };
+        assert_eq!(missing_input.exit_code(), 66);
+        assert_eq!(
+            missing_input.hint(),
+            Some(
+                "run `cargo download 05 --year 2023` or paste your puzzle input into the file."
+                    .into()
+            )
+        );
+
+        assert_eq!(TemplateError::Usage("x".into()).exit_code(), 64);
+        assert_eq!(TemplateError::CheckFailed("x".into()).exit_code(), 1);
+        assert_eq!(
+            TemplateError::from(AocClientError::SessionNotFound).exit_code(),
+            69
+        );
+        assert_eq!(
+            TemplateError::CommandFailed {
+                command: "cargo run".into(),
+                code: Some(101)
+            }
+            .exit_code(),
+            101
+        );
+    }
+}
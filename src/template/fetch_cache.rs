@@ -0,0 +1,192 @@
+/// Keeps track of when inputs and puzzles were fetched and when the last request was sent,
+/// so that requests to adventofcode.com can be throttled across separate invocations.
+use std::{
+    collections::HashMap,
+    fs,
+    io::Error,
+    str::FromStr,
+    thread,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::config;
+
+static CACHE_FILE_NAME: &str = ".fetch_cache.json";
+
+/// Minimum time between two requests to adventofcode.com.
+pub const MIN_REQUEST_INTERVAL: Duration = Duration::from_secs(3);
+
+#[derive(Clone, Debug, Default)]
+pub struct FetchCache {
+    /// Unix timestamp in milliseconds of the last outbound request.
+    pub last_request: Option<u64>,
+    /// Unix timestamp in seconds of the last fetch of a resource, e.g. `inputs/01`.
+    pub fetched_at: HashMap<String, u64>,
+}
+
+impl FetchCache {
+    /// Dehydrate the cache to a JSON file.
+    pub fn store_file(&self) -> Result<(), Error> {
+        let json = JsonValue::from(self);
+        let mut file = fs::File::create(config::get().paths.data_file(CACHE_FILE_NAME))?;
+        json.format_to(&mut file)
+    }
+
+    /// Rehydrate the cache from a JSON file. If not present or malformed, returns an empty cache.
+    pub fn read_from_file() -> Self {
+        fs::read_to_string(config::get().paths.data_file(CACHE_FILE_NAME))
+            .map_err(|x| x.to_string())
+            .and_then(FetchCache::try_from)
+            .unwrap_or_default()
+    }
+
+    /// Records that a resource was fetched just now.
+    pub fn record_fetch(&mut self, key: &str) {
+        self.fetched_at.insert(key.into(), now().as_secs());
+    }
+
+    /// Returns how long to wait before the next request may be sent.
+    pub fn time_until_next_request(&self, interval: Duration, now: Duration) -> Duration {
+        self.last_request
+            .map(Duration::from_millis)
+            .map_or(Duration::ZERO, |last| (last + interval).saturating_sub(now))
+    }
+}
+
+/// Blocks until the minimum interval since the last request has passed, then marks a request as sent.
+pub fn wait_for_request_slot(interval: Duration) {
+    let mut cache = FetchCache::read_from_file();
+    let wait = cache.time_until_next_request(interval, now());
+
+    if !wait.is_zero() {
+        eprintln!("Waiting {wait:.1?} before sending the next request...");
+        thread::sleep(wait);
+    }
+
+    cache.last_request = u64::try_from(now().as_millis()).ok();
+
+    if let Err(e) = cache.store_file() {
+        eprintln!("Failed to update fetch cache: {e}");
+    }
+}
+
+/// Records the fetch of a resource in the cache file.
+pub fn record_fetch(key: &str) {
+    let mut cache = FetchCache::read_from_file();
+    cache.record_fetch(key);
+
+    if let Err(e) = cache.store_file() {
+        eprintln!("Failed to update fetch cache: {e}");
+    }
+}
+
+fn now() -> Duration {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .unwrap_or_default()
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&FetchCache> for JsonValue {
+    fn from(value: &FetchCache) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "last_request".into(),
+            value
+                .last_request
+                .map_or(JsonValue::Null, |x| JsonValue::Number(x as f64)),
+        );
+        map.insert(
+            "fetched_at".into(),
+            JsonValue::Object(
+                value
+                    .fetched_at
+                    .iter()
+                    .map(|(k, v)| (k.clone(), JsonValue::Number(*v as f64)))
+                    .collect(),
+            ),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for FetchCache {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?;
+
+        let last_request = json
+            .get("last_request")
+            .map(|v| if v.is_null() { None } else { v.get::<f64>() })
+            .ok_or("Expected cache.last_request to be null or number.")?;
+
+        let fetched_at = json
+            .get("fetched_at")
+            .and_then(|v| v.get::<HashMap<String, JsonValue>>())
+            .ok_or("Expected cache.fetched_at to be an object.")?
+            .iter()
+            .map(|(k, v)| {
+                v.get::<f64>()
+                    .map(|v| (k.clone(), *v as u64))
+                    .ok_or(format!("Expected cache.fetched_at.{k} to be a number."))
+            })
+            .collect::<Result<_, _>>()?;
+
+        Ok(FetchCache {
+            last_request: last_request.map(|x| *x as u64),
+            fetched_at,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::FetchCache;
+    use std::time::Duration;
+
+    #[test]
+    fn computes_wait_time() {
+        let interval = Duration::from_secs(3);
+        let mut cache = FetchCache::default();
+        assert_eq!(
+            cache.time_until_next_request(interval, Duration::from_secs(100)),
+            Duration::ZERO
+        );
+
+        cache.last_request = Some(99_000);
+        assert_eq!(
+            cache.time_until_next_request(interval, Duration::from_secs(100)),
+            Duration::from_secs(2)
+        );
+        assert_eq!(
+            cache.time_until_next_request(interval, Duration::from_secs(110)),
+            Duration::ZERO
+        );
+    }
+
+    #[test]
+    fn roundtrips_cache() {
+        let mut cache = FetchCache {
+            last_request: Some(1_702_044_180_123),
+            ..FetchCache::default()
+        };
+        cache.fetched_at.insert("inputs/01".into(), 1_702_044_180);
+
+        let json = tinyjson::JsonValue::from(&cache).stringify().unwrap();
+        let cache = FetchCache::try_from(json).unwrap();
+        assert_eq!(cache.last_request, Some(1_702_044_180_123));
+        assert_eq!(cache.fetched_at.get("inputs/01"), Some(&1_702_044_180));
+    }
+}
@@ -0,0 +1,352 @@
+/// Append-only history of benchmark runs, stored as one JSON document per line.
+/// `timings.json` remains the latest snapshot that the README is rendered from.
+use std::{
+    collections::HashMap,
+    fs::{self, OpenOptions},
+    io::{Error, Write},
+    process::Command,
+    str::FromStr,
+    time::{Duration, SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::config;
+use crate::template::timings::{Timing, Timings};
+use crate::template::{PuzzleId, ANSI_BOLD, ANSI_RESET};
+
+static HISTORY_FILE_NAME: &str = "timings_history.jsonl";
+
+/// A single benchmark run.
+#[derive(Clone, Debug)]
+pub struct HistoryEntry {
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+    /// Abbreviated hash of the checked out commit, suffixed with `-dirty` if there are local changes.
+    pub commit: Option<String>,
+    /// Cargo profile the solutions were compiled with.
+    pub profile: String,
+    pub timings: Timings,
+}
+
+impl HistoryEntry {
+    /// Creates an entry for a run that just finished.
+    pub fn now(timings: Timings) -> Self {
+        Self {
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+            commit: get_commit(),
+            profile: if cfg!(debug_assertions) {
+                "debug".into()
+            } else {
+                "release".into()
+            },
+            timings,
+        }
+    }
+}
+
+/// Append an entry to the history file.
+pub fn append(entry: &HistoryEntry) -> Result<(), Error> {
+    let line = JsonValue::from(entry)
+        .stringify()
+        .map_err(|e| Error::other(e.to_string()))?;
+
+    let mut file = OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(config::get().paths.data_file(HISTORY_FILE_NAME))?;
+
+    writeln!(file, "{line}")
+}
+
+/// Read all entries from the history file, oldest first. If not present, returns no entries.
+pub fn read_from_file() -> Vec<HistoryEntry> {
+    let Ok(s) = fs::read_to_string(config::get().paths.data_file(HISTORY_FILE_NAME)) else {
+        return vec![];
+    };
+
+    s.lines()
+        .filter(|line| !line.trim().is_empty())
+        .filter_map(|line| match HistoryEntry::try_from(line) {
+            Ok(entry) => Some(entry),
+            Err(e) => {
+                eprintln!("Skipping malformed history entry: {e}");
+                None
+            }
+        })
+        .collect()
+}
+
+/// Returns the timings of each day from its second to last run in the history.
+pub fn previous_timings(entries: &[HistoryEntry]) -> Timings {
+    let mut runs: HashMap<PuzzleId, Vec<&Timing>> = HashMap::new();
+
+    for timing in entries.iter().flat_map(|entry| &entry.timings.data) {
+        runs.entry(timing.id).or_default().push(timing);
+    }
+
+    let mut data: Vec<Timing> = runs
+        .into_values()
+        .filter_map(|runs| runs.len().checked_sub(2).map(|i| runs[i].clone()))
+        .collect();
+
+    data.sort_unstable_by_key(|t| t.id);
+    Timings { data }
+}
+
+/// Print the timings of a day for every run in the history.
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+pub fn print_day_history(entries: &[HistoryEntry], id: PuzzleId) {
+    println!("{ANSI_BOLD}{} Day {} history{ANSI_RESET}", id.year, id.day);
+    println!("------");
+
+    let rows: Vec<(&HistoryEntry, &Timing)> = entries
+        .iter()
+        .filter_map(|entry| Some((entry, entry.timings.data.iter().find(|t| t.id == id)?)))
+        .collect();
+
+    if rows.is_empty() {
+        println!("No benchmark runs recorded.");
+        return;
+    }
+
+    let mut previous_total: Option<f64> = None;
+
+    for (entry, timing) in rows {
+        let trend = match previous_total {
+            Some(previous) if previous > 0.0 => {
+                format!(
+                    " ({:+.1}%)",
+                    (timing.total_nanos - previous) / previous * 100.0
+                )
+            }
+            _ => String::new(),
+        };
+        previous_total = Some(timing.total_nanos);
+
+        println!(
+            "{} | {} | {} | parse {} | part 1 {} | part 2 {} | total {:.1?}{trend}",
+            format_timestamp(entry.timestamp),
+            entry.commit.as_deref().unwrap_or("-"),
+            entry.profile,
+            timing.parse.as_deref().unwrap_or("-"),
+            timing.part_1.as_deref().unwrap_or("-"),
+            timing.part_2.as_deref().unwrap_or("-"),
+            Duration::from_nanos(timing.total_nanos as u64),
+        );
+    }
+}
+
+fn get_commit() -> Option<String> {
+    let output = Command::new("git")
+        .args(["rev-parse", "--short", "HEAD"])
+        .output()
+        .ok()?;
+
+    if !output.status.success() {
+        return None;
+    }
+
+    let hash = String::from_utf8_lossy(&output.stdout).trim().to_string();
+
+    let is_dirty = Command::new("git")
+        .args(["status", "--porcelain"])
+        .output()
+        .is_ok_and(|output| !output.stdout.is_empty());
+
+    Some(if is_dirty {
+        format!("{hash}-dirty")
+    } else {
+        hash
+    })
+}
+
+/// Formats a unix timestamp as a UTC date, e.g. `2023-12-08 14:03`.
+pub(crate) fn format_timestamp(timestamp: u64) -> String {
+    let days = timestamp / 86400;
+    let seconds = timestamp % 86400;
+
+    // see: http://howardhinnant.github.io/date_algorithms.html#civil_from_days
+    let z = days + 719_468;
+    let era = z / 146_097;
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let day = doy - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + u64::from(month <= 2);
+
+    format!(
+        "{year:04}-{month:02}-{day:02} {:02}:{:02}",
+        seconds / 3600,
+        seconds % 3600 / 60
+    )
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&HistoryEntry> for JsonValue {
+    fn from(value: &HistoryEntry) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+        map.insert(
+            "commit".into(),
+            value
+                .commit
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("profile".into(), JsonValue::String(value.profile.clone()));
+        map.insert("timings".into(), JsonValue::from(value.timings.clone()));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&str> for HistoryEntry {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &str) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(value).or(Err("not valid JSON."))?;
+
+        let json = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected history entry to be a JSON object.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>().copied())
+            .ok_or("Expected entry.timestamp to be a number.")?;
+
+        let commit = json
+            .get("commit")
+            .map(|v| if v.is_null() { None } else { v.get::<String>() })
+            .ok_or("Expected entry.commit to be null or string.")?;
+
+        let profile = json
+            .get("profile")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected entry.profile to be a string.")?;
+
+        let timings = json
+            .get("timings")
+            .ok_or("Expected entry to have key `timings`.")?
+            .stringify()
+            .map_err(|e| e.to_string())
+            .and_then(Timings::try_from)?;
+
+        Ok(HistoryEntry {
+            timestamp: timestamp as u64,
+            commit: commit.cloned(),
+            profile: profile.clone(),
+            timings,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{format_timestamp, previous_timings, HistoryEntry};
+    use crate::{
+        puzzle,
+        template::timings::{Timing, Timings},
+        template::{Day, PuzzleId},
+        year,
+    };
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn formats_timestamps() {
+        assert_eq!(format_timestamp(0), "1970-01-01 00:00");
+        assert_eq!(format_timestamp(1_702_044_180), "2023-12-08 14:03");
+        assert_eq!(format_timestamp(1_709_208_000), "2024-02-29 12:00");
+    }
+
+    #[test]
+    fn roundtrips_entries() {
+        let entry = HistoryEntry {
+            timestamp: 1_702_044_180,
+            commit: Some("8a2df08".into()),
+            profile: "release".into(),
+            timings: Timings {
+                data: vec![Timing {
+                    id: puzzle!(2023, 8),
+                    parse: Some("1.7ms".into()),
+                    part_1: Some("1.0ms".into()),
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
+                    total_nanos: 2_700_000_f64,
+                }],
+            },
+        };
+
+        let line = JsonValue::from(&entry).stringify().unwrap();
+        assert!(!line.contains('\n'));
+
+        let parsed = HistoryEntry::try_from(line.as_str()).unwrap();
+        assert_eq!(parsed.timestamp, entry.timestamp);
+        assert_eq!(parsed.commit, entry.commit);
+        assert_eq!(parsed.profile, "release");
+        assert_eq!(parsed.timings.data[0].id, puzzle!(2023, 8));
+        assert_eq!(parsed.timings.data[0].parse, Some("1.7ms".into()));
+    }
+
+    #[test]
+    #[should_panic]
+    fn panics_for_malformed_entries() {
+        HistoryEntry::try_from(r#"{ "timestamp": 1, "profile": "release" }"#).unwrap();
+    }
+
+    #[test]
+    fn finds_previous_timings() {
+        let entry = |days: &[(u8, f64)]| HistoryEntry {
+            timestamp: 0,
+            commit: None,
+            profile: "release".into(),
+            timings: Timings {
+                data: days
+                    .iter()
+                    .map(|(day, total_nanos)| Timing {
+                        id: PuzzleId::new(year!(2023), Day::new(*day).unwrap()),
+                        parse: None,
+                        part_1: None,
+                        part_2: None,
+                        parse_stats: None,
+                        part_1_stats: None,
+                        part_2_stats: None,
+                        part_1_memory: None,
+                        part_2_memory: None,
+                        stale_parts: vec![],
+                        total_nanos: *total_nanos,
+                    })
+                    .collect(),
+            },
+        };
+
+        let entries = [
+            entry(&[(1, 30.0), (2, 50.0)]),
+            entry(&[(1, 20.0)]),
+            entry(&[(1, 10.0), (3, 5.0)]),
+        ];
+
+        let previous = previous_timings(&entries);
+        assert_eq!(previous.data.len(), 1);
+        assert_eq!(previous.data[0].id, puzzle!(2023, 1));
+        assert_eq!(previous.data[0].total_nanos, 20.0);
+    }
+}
@@ -0,0 +1,243 @@
+/// Minimal HTML to Markdown conversion for puzzle descriptions.
+/// Only covers the small subset of HTML that is used on adventofcode.com.
+use std::fmt::Write;
+
+/// Returns the inner HTML of every `<article>` element in a document.
+pub fn extract_articles(html: &str) -> Vec<&str> {
+    let mut articles = vec![];
+    let mut rest = html;
+
+    while let Some(start) = rest.find("<article") {
+        let Some(content_start) = rest[start..].find('>').map(|i| start + i + 1) else {
+            break;
+        };
+        let Some(end) = rest[content_start..]
+            .find("</article>")
+            .map(|i| content_start + i)
+        else {
+            break;
+        };
+        articles.push(&rest[content_start..end]);
+        rest = &rest[end + "</article>".len()..];
+    }
+
+    articles
+}
+
+/// Converts a fragment of HTML to Markdown.
+pub fn to_markdown(html: &str) -> String {
+    let mut out = String::new();
+    let mut links: Vec<String> = vec![];
+    let mut in_pre = false;
+    let mut code_depth = 0;
+    // emphasized inline code, e.g. an example answer, is written as *`288`*.
+    let mut is_code_emphasized = false;
+    let mut rest = html;
+
+    while !rest.is_empty() {
+        if let Some(tag_content) = rest.strip_prefix('<') {
+            let Some(end) = tag_content.find('>') else {
+                out.push_str(&decode_entities(rest));
+                break;
+            };
+            let tag = &tag_content[..end];
+            rest = &tag_content[end + 1..];
+
+            let is_closing = tag.starts_with('/');
+            let name = tag
+                .trim_start_matches('/')
+                .split(|c: char| c.is_whitespace() || c == '/')
+                .next()
+                .unwrap_or_default()
+                .to_ascii_lowercase();
+
+            match (name.as_str(), is_closing) {
+                ("h2", false) => out.push_str("## "),
+                ("h2" | "p" | "ul", true) => end_block(&mut out),
+                ("pre", false) => {
+                    in_pre = true;
+                    out.push_str("```\n");
+                }
+                ("pre", true) => {
+                    in_pre = false;
+                    if !out.ends_with('\n') {
+                        out.push('\n');
+                    }
+                    out.push_str("```");
+                    end_block(&mut out);
+                }
+                ("code", false) => {
+                    code_depth += 1;
+                    if !in_pre {
+                        out.push('`');
+                    }
+                }
+                ("code", true) => {
+                    code_depth -= 1;
+                    if !in_pre {
+                        out.push('`');
+                        if is_code_emphasized {
+                            out.push('*');
+                            is_code_emphasized = false;
+                        }
+                    }
+                }
+                ("em", false) if !in_pre && code_depth > 0 && out.ends_with('`') => {
+                    out.insert(out.len() - 1, '*');
+                    is_code_emphasized = true;
+                }
+                ("em", _) if !in_pre && code_depth == 0 => out.push('*'),
+                ("li", false) => out.push_str("- "),
+                ("li", true) => out.push('\n'),
+                ("br", _) => out.push('\n'),
+                ("a", false) => {
+                    links.push(get_attribute(tag, "href").unwrap_or_default());
+                    out.push('[');
+                }
+                ("a", true) => {
+                    let href = links.pop().unwrap_or_default();
+                    let _ = write!(out, "]({href})");
+                }
+                _ => {}
+            }
+        } else {
+            let end = rest.find('<').unwrap_or(rest.len());
+            let text = decode_entities(&rest[..end]);
+            rest = &rest[end..];
+
+            if in_pre {
+                out.push_str(&text);
+            } else {
+                let text = text.replace('\n', " ");
+                // skip formatting whitespace between block elements.
+                if !(text.trim().is_empty() && (out.is_empty() || out.ends_with('\n'))) {
+                    out.push_str(&text);
+                }
+            }
+        }
+    }
+
+    format!("{}\n", out.trim_end())
+}
+
+/// Ends a block element with exactly one blank line.
+fn end_block(out: &mut String) {
+    if out.is_empty() {
+        return;
+    }
+    out.truncate(out.trim_end_matches(' ').len());
+    while !out.ends_with("\n\n") {
+        out.push('\n');
+    }
+}
+
+/// Converts a fragment of HTML to plain text.
+pub fn to_text(html: &str) -> String {
+    let mut out = String::new();
+    let mut rest = html;
+
+    while let Some(start) = rest.find('<') {
+        out.push_str(&decode_entities(&rest[..start]));
+        match rest[start..].find('>') {
+            Some(end) => rest = &rest[start + end + 1..],
+            None => {
+                rest = "";
+            }
+        }
+    }
+
+    out.push_str(&decode_entities(rest));
+    out.trim().to_string()
+}
+
+fn get_attribute(tag: &str, name: &str) -> Option<String> {
+    let pattern = format!("{name}=\"");
+    let start = tag.find(&pattern)? + pattern.len();
+    let end = tag[start..].find('"')? + start;
+    Some(decode_entities(&tag[start..end]))
+}
+
+fn decode_entities(s: &str) -> String {
+    let mut out = String::with_capacity(s.len());
+    let mut rest = s;
+
+    while let Some(start) = rest.find('&') {
+        out.push_str(&rest[..start]);
+        rest = &rest[start..];
+
+        let decoded = rest.find(';').and_then(|end| {
+            let entity = &rest[1..end];
+            let c = match entity {
+                "amp" => Some('&'),
+                "lt" => Some('<'),
+                "gt" => Some('>'),
+                "quot" => Some('"'),
+                "apos" => Some('\''),
+                "nbsp" => Some(' '),
+                _ => entity
+                    .strip_prefix("#x")
+                    .and_then(|hex| u32::from_str_radix(hex, 16).ok())
+                    .or_else(|| entity.strip_prefix('#').and_then(|n| n.parse().ok()))
+                    .and_then(char::from_u32),
+            }?;
+            Some((c, end))
+        });
+
+        match decoded {
+            Some((c, end)) => {
+                out.push(c);
+                rest = &rest[end + 1..];
+            }
+            None => {
+                out.push('&');
+                rest = &rest[1..];
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{extract_articles, to_markdown, to_text};
+
+    #[test]
+    fn extracts_articles() {
+        let html = r#"<main><article class="day-desc"><h2>One</h2></article><p>x</p><article class="day-desc"><h2>Two</h2></article></main>"#;
+        assert_eq!(extract_articles(html), vec!["<h2>One</h2>", "<h2>Two</h2>"]);
+    }
+
+    #[test]
+    fn converts_puzzle_descriptions() {
+        let html = concat!(
+            "<h2>--- Day 6: Wait For It ---</h2>",
+            "<p>The <em>boat</em> race &amp; a <a href=\"/2023/about\" target=\"_blank\">link</a>.</p>\n",
+            "<pre><code>Time:      7  15   30\nDistance:  9  40  200\n</code></pre>\n",
+            "<ul>\n<li>Hold for <code><em>1</em></code> ms.</li>\n<li>Win &lt;3</li>\n</ul>\n",
+            "<p>Done.</p>"
+        );
+
+        assert_eq!(
+            to_markdown(html),
+            concat!(
+                "## --- Day 6: Wait For It ---\n\n",
+                "The *boat* race & a [link](/2023/about).\n\n",
+                "```\nTime:      7  15   30\nDistance:  9  40  200\n```\n\n",
+                "- Hold for *`1`* ms.\n- Win <3\n\n",
+                "Done.\n"
+            )
+        );
+    }
+
+    #[test]
+    fn converts_to_text() {
+        assert_eq!(
+            to_text("<p>That's the <em>right</em> answer! &#x1F384;</p>\n"),
+            "That's the right answer! 🎄"
+        );
+    }
+}
@@ -1,68 +1,166 @@
-use std::{env, fs};
+use std::{env, fs, path::PathBuf};
 
-pub mod aoc_cli;
+pub mod aoc_client;
 pub mod commands;
+pub mod config;
+pub mod error;
 pub mod runner;
+pub mod stats;
 
 pub use day::*;
+pub use year::*;
 
+mod answers;
+mod compare;
 mod day;
+mod fetch_cache;
+mod history;
+mod html;
+mod output;
+mod puzzle;
 mod readme_benchmarks;
 mod run_multi;
+mod submissions;
 mod timings;
+mod year;
 
 pub const ANSI_ITALIC: &str = "\x1b[3m";
 pub const ANSI_BOLD: &str = "\x1b[1m";
 pub const ANSI_RESET: &str = "\x1b[0m";
+pub const ANSI_RED: &str = "\x1b[31m";
+pub const ANSI_GREEN: &str = "\x1b[32m";
 
-/// Helper function that reads a text file to a string.
+/// Helper function that returns the path of a data folder of a year, e.g. `data/2023/inputs`.
+/// The data folder can be changed with `paths.data` in `aoc.toml`.
+#[must_use]
+pub fn get_data_folder(folder: &str, year: Year) -> PathBuf {
+    config::get().paths.data.join(year.to_string()).join(folder)
+}
+
+/// Helper function that returns the path of a puzzle's text file in a data folder, e.g. `data/2023/inputs/01.txt`.
 #[must_use]
-pub fn read_file(folder: &str, day: Day) -> String {
+pub fn get_data_path(folder: &str, id: PuzzleId) -> PathBuf {
     let cwd = env::current_dir().unwrap();
-    let filepath = cwd.join("data").join(folder).join(format!("{day}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+    cwd.join(get_data_folder(folder, id.year))
+        .join(format!("{}.txt", id.day))
+}
+
+/// Helper function that reads a text file to a string.
+#[must_use]
+pub fn read_file(folder: &str, id: PuzzleId) -> String {
+    let filepath = get_data_path(folder, id);
+    fs::read_to_string(&filepath)
+        .unwrap_or_else(|e| panic!("could not open file \"{}\": {e}", filepath.display()))
 }
 
 /// Helper function that reads a text file to string, appending a part suffix. E.g. like `01-2.txt`.
 #[must_use]
-pub fn read_file_part(folder: &str, day: Day, part: u8) -> String {
+pub fn read_file_part(folder: &str, id: PuzzleId, part: u8) -> String {
     let cwd = env::current_dir().unwrap();
     let filepath = cwd
-        .join("data")
-        .join(folder)
-        .join(format!("{day}-{part}.txt"));
-    let f = fs::read_to_string(filepath);
-    f.expect("could not open input file")
+        .join(get_data_folder(folder, id.year))
+        .join(format!("{}-{part}.txt", id.day));
+    fs::read_to_string(&filepath)
+        .unwrap_or_else(|e| panic!("could not open file \"{}\": {e}", filepath.display()))
 }
 
-/// Creates the constant `DAY` and sets up the input and runner for each part.
+/// Creates the constants `YEAR`, `DAY` and `PUZZLE` and sets up the input and runner for each part.
+///
+/// The year is passed with a leading `year: <year>`, e.g. `solution!(year: 2023, 1)`. If omitted,
+/// the `AOC_YEAR` environment variable at compile time is used, which defaults to the `year` set in
+/// `aoc.toml`.
 ///
 /// The optional, second parameter (1 or 2) allows you to only run a single part of the solution.
+///
+/// Passing `parse: <function>` parses the input once before running the parts. The parts then
+/// receive a reference to the parsed input and the parse step is timed separately, e.g.
+/// `solution!(year: 2023, 4, parse: parse_cards)` or `solution!(year: 2023, 4, parse: parse_cards, 1)`.
+///
+/// Passing `timeout: <seconds>` before the day overrides the time limit of each part, e.g.
+/// `solution!(year: 2023, timeout: 300, 8)`.
 #[macro_export]
 macro_rules! solution {
-    ($day:expr) => {
-        $crate::solution!(@impl $day, [part_one, 1] [part_two, 2]);
+    (year: $year:expr, $($rest:tt)*) => {
+        $crate::solution!(@year $crate::year!($year); $($rest)*);
+    };
+
+    (@year $year:expr; timeout: $secs:expr, $($rest:tt)*) => {
+        $crate::solution!(@opts $year, Some(::std::time::Duration::from_secs($secs)); $($rest)*);
+    };
+    (@year $year:expr; $($rest:tt)*) => {
+        $crate::solution!(@opts $year, None; $($rest)*);
     };
-    ($day:expr, 1) => {
-        $crate::solution!(@impl $day, [part_one, 1]);
+
+    (@opts $year:expr, $timeout:expr; $day:expr) => {
+        $crate::solution!(@impl $year, $day, $timeout, [part_one, 1] [part_two, 2]);
+    };
+    (@opts $year:expr, $timeout:expr; $day:expr, 1) => {
+        $crate::solution!(@impl $year, $day, $timeout, [part_one, 1]);
+    };
+    (@opts $year:expr, $timeout:expr; $day:expr, 2) => {
+        $crate::solution!(@impl $year, $day, $timeout, [part_two, 2]);
+    };
+    (@opts $year:expr, $timeout:expr; $day:expr, parse: $parse:expr) => {
+        $crate::solution!(@impl_parsed $year, $day, $timeout, $parse, [part_one, 1] [part_two, 2]);
     };
-    ($day:expr, 2) => {
-        $crate::solution!(@impl $day, [part_two, 2]);
+    (@opts $year:expr, $timeout:expr; $day:expr, parse: $parse:expr, 1) => {
+        $crate::solution!(@impl_parsed $year, $day, $timeout, $parse, [part_one, 1]);
+    };
+    (@opts $year:expr, $timeout:expr; $day:expr, parse: $parse:expr, 2) => {
+        $crate::solution!(@impl_parsed $year, $day, $timeout, $parse, [part_two, 2]);
     };
 
-    (@impl $day:expr, $( [$func:expr, $part:expr] )*) => {
+    (@consts $year:expr, $day:expr) => {
+        /// The year of the current puzzle.
+        const YEAR: $crate::template::Year = $year;
         /// The current day.
         const DAY: $crate::template::Day = $crate::day!($day);
+        /// The current puzzle.
+        const PUZZLE: $crate::template::PuzzleId = $crate::template::PuzzleId::new(YEAR, DAY);
 
-        #[cfg(feature = "dhat-heap")]
-        #[global_allocator]
-        static ALLOC: dhat::Alloc = dhat::Alloc;
-
+        #[allow(dead_code)]
         fn main() {
-            use $crate::template::runner::*;
-            let input = $crate::template::read_file("inputs", DAY);
-            $( run_part($func, &input, DAY, $part); )*
+            $crate::template::runner::run_main(&SOLUTION);
         }
     };
+
+    (@impl $year:expr, $day:expr, $timeout:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $year, $day);
+
+        /// The solution for the current day, used by the in-process multi-day runner.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            id: PUZZLE,
+            run: |input, options| {
+                use $crate::template::runner::*;
+                let mut result = DayResult::new(PUZZLE);
+                $( result.parts.push(run_part($func, input, PUZZLE, $part, options)); )*
+                result
+            },
+            timeout: $timeout,
+        };
+    };
+
+    (@impl_parsed $year:expr, $day:expr, $timeout:expr, $parse:expr, $( [$func:expr, $part:expr] )*) => {
+        $crate::solution!(@consts $year, $day);
+
+        /// The solution for the current day, used by the in-process multi-day runner.
+        #[allow(dead_code)]
+        pub const SOLUTION: $crate::template::runner::Solution = $crate::template::runner::Solution {
+            id: PUZZLE,
+            run: |input, options| {
+                use $crate::template::runner::*;
+                let mut result = DayResult::new(PUZZLE);
+                let (parsed, parse_stats) = run_parse($parse, input, PUZZLE, options);
+                result.parse = Some(parse_stats);
+                $( result.parts.push(run_part($func, &parsed, PUZZLE, $part, options)); )*
+                result
+            },
+            timeout: $timeout,
+        };
+    };
+
+    ($($rest:tt)*) => {
+        $crate::solution!(@year $crate::template::Year::__from_env_unchecked(option_env!("AOC_YEAR")); $($rest)*);
+    };
 }
@@ -0,0 +1,126 @@
+/// Output of the runner. Writes to stdout and stderr directly, unless the current thread
+/// captures its output, e.g. when days run in parallel and have to be printed in order.
+use std::{
+    cell::RefCell,
+    fmt,
+    io::{self, Write},
+    mem,
+    sync::{Arc, Mutex},
+};
+
+/// Output captured while running a single day.
+#[derive(Debug, Default)]
+pub(crate) struct Captured {
+    stdout: String,
+    stderr: String,
+}
+
+impl Captured {
+    /// Writes the captured output to stdout and stderr.
+    pub(crate) fn print(&self) {
+        print!("{}", self.stdout);
+        eprint!("{}", self.stderr);
+        let _ = io::stdout().flush();
+    }
+}
+
+/// A capture that is shared between a day and the thread its solution runs on.
+pub(crate) type Capture = Arc<Mutex<Captured>>;
+
+thread_local! {
+    static CAPTURE: RefCell<Option<Capture>> = const { RefCell::new(None) };
+}
+
+/// Returns the capture of the current thread, if there is one.
+pub(crate) fn current() -> Option<Capture> {
+    CAPTURE.with(|capture| capture.borrow().clone())
+}
+
+/// Sets the capture of the current thread, used to pass it on to spawned threads.
+pub(crate) fn set(capture: Option<Capture>) {
+    CAPTURE.with(|current| *current.borrow_mut() = capture);
+}
+
+/// Runs `f`, capturing everything it writes with [`out!`], [`outln!`] and [`errln!`].
+pub(crate) fn capture<T>(f: impl FnOnce() -> T) -> (T, Captured) {
+    let capture = Capture::default();
+    set(Some(capture.clone()));
+    let result = f();
+    set(None);
+
+    // NOTE: a timed out solution may still hold on to the capture, so take its contents.
+    let captured = mem::take(&mut *capture.lock().unwrap());
+    (result, captured)
+}
+
+pub(crate) fn write_stdout(args: fmt::Arguments) {
+    match current() {
+        Some(capture) => {
+            let _ = fmt::Write::write_fmt(&mut capture.lock().unwrap().stdout, args);
+        }
+        None => {
+            let mut stdout = io::stdout().lock();
+            let _ = stdout.write_fmt(args);
+            let _ = stdout.flush();
+        }
+    }
+}
+
+pub(crate) fn write_stderr(args: fmt::Arguments) {
+    match current() {
+        Some(capture) => {
+            let _ = fmt::Write::write_fmt(&mut capture.lock().unwrap().stderr, args);
+        }
+        None => {
+            let _ = io::stderr().write_fmt(args);
+        }
+    }
+}
+
+/// Like `print!`, but respects the capture of the current thread.
+macro_rules! out {
+    ($($arg:tt)*) => {
+        $crate::template::output::write_stdout(format_args!($($arg)*))
+    };
+}
+
+/// Like `println!`, but respects the capture of the current thread.
+macro_rules! outln {
+    () => {
+        $crate::template::output::write_stdout(format_args!("\n"))
+    };
+    ($($arg:tt)*) => {
+        $crate::template::output::write_stdout(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+/// Like `eprintln!`, but respects the capture of the current thread.
+macro_rules! errln {
+    ($($arg:tt)*) => {
+        $crate::template::output::write_stderr(format_args!("{}\n", format_args!($($arg)*)))
+    };
+}
+
+pub(crate) use {errln, out, outln};
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{capture, current};
+
+    #[test]
+    fn captures_output() {
+        let (result, captured) = capture(|| {
+            out!("Part 1: ");
+            outln!("{}", 42);
+            errln!("Error: {}", "oops");
+            7
+        });
+
+        assert_eq!(result, 7);
+        assert_eq!(captured.stdout, "Part 1: 42\n");
+        assert_eq!(captured.stderr, "Error: oops\n");
+        assert!(current().is_none());
+    }
+}
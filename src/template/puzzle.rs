@@ -0,0 +1,207 @@
+/// Extracts the examples and their answers from a downloaded puzzle description.
+/// Puzzles do not mark their examples, so this is a best guess: the example is the code block
+/// introduced by a paragraph that mentions an example, the answer is the last emphasized code.
+use std::{fs, io, path::PathBuf};
+
+use crate::template::{get_data_folder, PuzzleId};
+
+/// The example of a part and the answer the puzzle gives for it.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct Example {
+    pub input: Option<String>,
+    /// Only set if the answer is an integer, so that it can be used in generated tests.
+    pub answer: Option<String>,
+}
+
+/// Returns the example of part one and, once it is unlocked, of part two.
+/// Part two only has an input if it introduces a new example.
+pub fn parse_examples(markdown: &str) -> Vec<Example> {
+    let (part_one, part_two) = match markdown.find("## --- Part Two ---") {
+        Some(index) => (&markdown[..index], Some(&markdown[index..])),
+        None => (markdown, None),
+    };
+
+    let mut examples = vec![parse_part(part_one, true)];
+
+    if let Some(part_two) = part_two {
+        let mut example = parse_part(part_two, false);
+        if example.input == examples[0].input {
+            example.input = None;
+        }
+        examples.push(example);
+    }
+
+    examples
+}
+
+fn parse_part(markdown: &str, is_first: bool) -> Example {
+    // pairs of the last paragraph before a code block and its content.
+    let mut blocks: Vec<(&str, String)> = vec![];
+    let mut text = String::new();
+    let mut paragraph = "";
+    let mut block: Option<String> = None;
+
+    for line in markdown.lines() {
+        if line == "```" {
+            match block.take() {
+                Some(content) => blocks.push((paragraph, content)),
+                None => block = Some(String::new()),
+            }
+        } else if let Some(block) = &mut block {
+            block.push_str(line);
+            block.push('\n');
+        } else {
+            if !line.trim().is_empty() {
+                paragraph = line;
+            }
+            text.push_str(line);
+            text.push('\n');
+        }
+    }
+
+    let is_example = |paragraph: &str| {
+        let paragraph = paragraph.to_lowercase();
+        paragraph.contains("example") && paragraph.trim_end().ends_with(':')
+    };
+
+    // NOTE: part one almost always starts with its example, part two usually reuses it.
+    let input = blocks
+        .iter()
+        .find(|(paragraph, _)| is_example(paragraph))
+        .or(blocks.first().filter(|_| is_first))
+        .map(|(_, input)| input.clone());
+
+    let answer = text
+        .split("*`")
+        .skip(1)
+        .filter_map(|s| s.split_once("`*").map(|(code, _)| code))
+        .last()
+        .filter(|code| {
+            let digits = code.strip_prefix('-').unwrap_or(code);
+            !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+        })
+        .map(String::from);
+
+    Example { input, answer }
+}
+
+/// Reads the examples from the downloaded puzzle description of a day, if there is one.
+pub fn read_examples(id: PuzzleId) -> Vec<Example> {
+    fs::read_to_string(get_data_folder("puzzles", id.year).join(format!("{}.md", id.day)))
+        .map(|markdown| parse_examples(&markdown))
+        .unwrap_or_default()
+}
+
+/// Returns the path of the example file of a part, e.g. `data/2023/examples/01-2.txt` for part two.
+pub fn get_example_path(id: PuzzleId, part: u8) -> PathBuf {
+    let name = match part {
+        1 => format!("{}.txt", id.day),
+        _ => format!("{}-{part}.txt", id.day),
+    };
+    get_data_folder("examples", id.year).join(name)
+}
+
+/// Writes the examples to the example files of a day, skipping files that already have content.
+/// Returns the paths of the written files.
+pub fn write_examples(id: PuzzleId, examples: &[Example]) -> Result<Vec<PathBuf>, io::Error> {
+    let mut written = vec![];
+
+    for (part, example) in (1..).zip(examples) {
+        let Some(input) = &example.input else {
+            continue;
+        };
+
+        let path = get_example_path(id, part);
+        if fs::metadata(&path).is_ok_and(|m| m.len() > 0) {
+            continue;
+        }
+
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        fs::write(&path, input)?;
+        written.push(path);
+    }
+
+    Ok(written)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{parse_examples, Example};
+
+    const PART_ONE: &str = concat!(
+        "## --- Day 6: Wait For It ---\n\n",
+        "You get a sheet of paper that lists the *time* allowed for each race.\n\n",
+        "For example:\n\n",
+        "```\nTime:      7  15   30\nDistance:  9  40  200\n```\n\n",
+        "This document describes three races:\n\n",
+        "- The first race lasts *`7`* milliseconds.\n\n",
+        "In this example, if you multiply these values together, you get *`288`* (`4` * `8` * `9`).\n\n",
+        "Determine the number of ways you could beat the record in each race. *What do you get if you multiply these numbers together?*\n",
+    );
+
+    #[test]
+    fn parses_part_one() {
+        assert_eq!(
+            parse_examples(PART_ONE),
+            vec![Example {
+                input: Some("Time:      7  15   30\nDistance:  9  40  200\n".into()),
+                answer: Some("288".into()),
+            }]
+        );
+    }
+
+    #[test]
+    fn parses_part_two() {
+        let markdown = format!(
+            "{PART_ONE}\n{}",
+            concat!(
+                "## --- Part Two ---\n\n",
+                "So, the example from before:\n\n",
+                "```\nTime:      7  15   30\nDistance:  9  40  200\n```\n\n",
+                "Now, you have to figure out how many ways there are to win this single race. ",
+                "In this example, you could hold the button anywhere from `14` to `71516` milliseconds, a total of *`71503`* ways!\n",
+            )
+        );
+
+        let examples = parse_examples(&markdown);
+        assert_eq!(examples.len(), 2);
+        assert_eq!(
+            examples[1],
+            Example {
+                input: None,
+                answer: Some("71503".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn parses_new_examples_of_part_two() {
+        let markdown = format!(
+            "{PART_ONE}\n{}",
+            concat!(
+                "## --- Part Two ---\n\n",
+                "Some of the digits are spelled out. For example:\n\n",
+                "```\ntwo1nine\neightwothree\n```\n\n",
+                "Adding these together produces *`281`*.\n",
+            )
+        );
+
+        assert_eq!(
+            parse_examples(&markdown)[1],
+            Example {
+                input: Some("two1nine\neightwothree\n".into()),
+                answer: Some("281".into()),
+            }
+        );
+    }
+
+    #[test]
+    fn ignores_non_numeric_answers() {
+        let markdown = "For example:\n\n```\nabc\n```\n\nThe result is *`ABC`*.\n";
+        assert_eq!(parse_examples(markdown)[0].answer, None);
+    }
+}
@@ -1,11 +1,15 @@
 /// Module that updates the readme me with timing information.
 /// The approach taken is similar to how `aoc-readme-stars` handles this.
-use std::{fs, io};
+use std::{fs, io, path::Path};
 
-use crate::template::timings::Timings;
-use crate::template::Day;
+use crate::template::config::{BarStyle, Column, ReadmeConfig, SortOrder};
+use crate::template::error::TemplateError;
+use crate::template::stats::{format_bytes, BenchStats};
+use crate::template::timings::{Timing, Timings};
+use crate::template::PuzzleId;
 
-static MARKER: &str = "<!--- benchmarking table --->";
+pub(crate) static MARKER: &str = "<!--- benchmarking table --->";
+pub(crate) static README_PATH: &str = "README.md";
 
 #[derive(Debug)]
 pub enum Error {
@@ -19,18 +23,33 @@ impl From<std::io::Error> for Error {
     }
 }
 
+impl Error {
+    fn into_template_error(self, path: &Path) -> TemplateError {
+        match self {
+            Error::Parser(reason) => TemplateError::MalformedData {
+                path: path.into(),
+                reason,
+            },
+            Error::IO(source) => TemplateError::Io {
+                path: path.into(),
+                source,
+            },
+        }
+    }
+}
+
 pub struct TablePosition {
     pos_start: usize,
     pos_end: usize,
 }
 
 #[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+pub fn get_path_for_bin(id: PuzzleId) -> String {
+    format!("./src/bin/{id}.rs")
 }
 
-fn locate_table(readme: &str) -> Result<TablePosition, Error> {
-    let matches: Vec<_> = readme.match_indices(MARKER).collect();
+fn locate_table(readme: &str, marker: &str) -> Result<TablePosition, Error> {
+    let matches: Vec<_> = readme.match_indices(marker).collect();
 
     if matches.len() > 2 {
         return Err(Error::Parser(
@@ -51,75 +70,261 @@ fn locate_table(readme: &str) -> Result<TablePosition, Error> {
     Ok(TablePosition { pos_start, pos_end })
 }
 
-fn construct_table(prefix: &str, timings: Timings, total_millis: f64) -> String {
-    let header = format!("{prefix} Benchmarks");
-
-    let mut lines: Vec<String> = vec![
-        MARKER.into(),
-        header,
-        String::new(),
-        "| Day | Part 1 | Part 2 |".into(),
-        "| :---: | :---: | :---:  |".into(),
-    ];
-
-    for timing in timings.data {
-        let path = get_path_for_bin(timing.day);
-        lines.push(format!(
-            "| [Day {}]({}) | `{}` | `{}` |",
-            timing.day.into_inner(),
-            path,
-            timing.part_1.unwrap_or_else(|| "-".into()),
-            timing.part_2.unwrap_or_else(|| "-".into())
-        ));
+fn construct_table(
+    timings: Timings,
+    total_millis: f64,
+    previous: &Timings,
+    config: &ReadmeConfig,
+) -> String {
+    let columns = config.columns.clone().unwrap_or_else(|| {
+        let mut columns = vec![];
+
+        // NOTE: only add a parse column if a solution times its parsing separately, the total
+        // includes the parse step either way.
+        if timings.data.iter().any(|t| t.parse.is_some()) {
+            columns.push(Column::Parse);
+        }
+
+        columns.extend([Column::Part1, Column::Part2]);
+
+        // NOTE: only add a memory column if memory usage was recorded with `cargo time --memory`.
+        if timings
+            .data
+            .iter()
+            .any(|t| t.part_1_memory.is_some() || t.part_2_memory.is_some())
+        {
+            columns.push(Column::Memory);
+        }
+
+        columns
+    });
+
+    let mut titles = vec!["Day"];
+    titles.extend(columns.iter().map(|column| column_title(*column)));
+    if config.bars.is_some() {
+        titles.push("Cost");
+    }
+
+    let mut lines: Vec<String> = vec![config.marker.clone(), config.title.clone(), String::new()];
+    lines.push(format!("| {} |", titles.join(" | ")));
+    lines.push(format!("|{}", " :---: |".repeat(titles.len())));
+
+    // NOTE: only prefix days with the year if the timings span more than one year.
+    let is_multi_year = timings
+        .data
+        .iter()
+        .any(|t| t.id.year != timings.data[0].id.year);
+
+    let max_nanos = timings
+        .data
+        .iter()
+        .map(|t| t.total_nanos)
+        .fold(0.0, f64::max);
+
+    let mut rows: Vec<&Timing> = timings.data.iter().collect();
+    if config.sort == SortOrder::Duration {
+        rows.sort_by(|a, b| b.total_nanos.total_cmp(&a.total_nanos));
+    }
+
+    for timing in rows {
+        let path = get_path_for_bin(timing.id);
+        let label = if is_multi_year {
+            format!("{} Day {}", timing.id.year, timing.id.day.into_inner())
+        } else {
+            format!("Day {}", timing.id.day.into_inner())
+        };
+
+        let mut cells = vec![format!("[{label}]({path})")];
+        cells.extend(
+            columns
+                .iter()
+                .map(|column| format!("`{}`", format_cell(*column, timing, previous))),
+        );
+
+        if let Some(style) = config.bars {
+            let fraction = if max_nanos > 0.0 {
+                timing.total_nanos / max_nanos
+            } else {
+                0.0
+            };
+            cells.push(format!(
+                "`{}`",
+                format_bar(fraction, config.bar_width, style)
+            ));
+        }
+
+        lines.push(format!("| {} |", cells.join(" | ")));
     }
 
-    lines.push(String::new());
-    lines.push(format!("**Total: {total_millis:.2}ms**"));
-    lines.push(MARKER.into());
+    if !config.footer.is_empty() {
+        lines.push(String::new());
+        lines.push(
+            config
+                .footer
+                .replace("{total}", &format!("{total_millis:.2}ms")),
+        );
+    }
+
+    lines.push(config.marker.clone());
 
     lines.join("\n")
 }
 
-fn update_content(s: &mut String, timings: Timings, total_millis: f64) -> Result<(), Error> {
-    let positions = locate_table(s)?;
-    let table = construct_table("##", timings, total_millis);
+fn column_title(column: Column) -> &'static str {
+    match column {
+        Column::Parse => "Parse",
+        Column::Part1 => "Part 1",
+        Column::Part2 => "Part 2",
+        Column::Samples => "Samples",
+        Column::Memory => "Peak memory",
+        Column::Speedup => "Speedup",
+    }
+}
+
+fn format_cell(column: Column, timing: &Timing, previous: &Timings) -> String {
+    let or_dash = |x: Option<String>| x.unwrap_or_else(|| "-".into());
+
+    match column {
+        Column::Parse => or_dash(timing.parse.clone()),
+        Column::Part1 => or_dash(timing.part_1.clone()),
+        Column::Part2 => or_dash(timing.part_2.clone()),
+        Column::Samples => {
+            let samples =
+                |stats: &Option<BenchStats>| or_dash(stats.as_ref().map(|s| s.samples.to_string()));
+            format!(
+                "{} / {}",
+                samples(&timing.part_1_stats),
+                samples(&timing.part_2_stats)
+            )
+        }
+        Column::Memory => or_dash(
+            [&timing.part_1_memory, &timing.part_2_memory]
+                .into_iter()
+                .flatten()
+                .map(|m| m.max_bytes)
+                .max()
+                .map(format_bytes),
+        ),
+        Column::Speedup => or_dash(
+            previous
+                .data
+                .iter()
+                .find(|t| t.id == timing.id)
+                .filter(|_| timing.total_nanos > 0.0)
+                .map(|t| format!("{:.2}×", t.total_nanos / timing.total_nanos)),
+        ),
+    }
+}
+
+/// Renders a bar for a fraction between 0 and 1, `width` characters long for a fraction of 1.
+#[allow(
+    clippy::cast_possible_truncation,
+    clippy::cast_sign_loss,
+    clippy::cast_precision_loss
+)]
+fn format_bar(fraction: f64, width: usize, style: BarStyle) -> String {
+    match style {
+        BarStyle::Ascii => {
+            let len = (fraction * width as f64).round() as usize;
+            "#".repeat(if fraction > 0.0 { len.max(1) } else { 0 })
+        }
+        BarStyle::Unicode => {
+            // NOTE: unicode bars have a resolution of an eighth of a character.
+            const PARTIAL: [&str; 8] = ["", "▏", "▎", "▍", "▌", "▋", "▊", "▉"];
+            let eighths = (fraction * width as f64 * 8.0).round() as usize;
+            let eighths = if fraction > 0.0 { eighths.max(1) } else { 0 };
+            format!("{}{}", "█".repeat(eighths / 8), PARTIAL[eighths % 8])
+        }
+    }
+}
+
+fn update_content(
+    s: &mut String,
+    timings: Timings,
+    total_millis: f64,
+    previous: &Timings,
+    config: &ReadmeConfig,
+) -> Result<(), Error> {
+    let positions = locate_table(s, &config.marker)?;
+    let table = construct_table(timings, total_millis, previous, config);
     s.replace_range(positions.pos_start..positions.pos_end, &table);
     Ok(())
 }
 
-pub fn update(timings: Timings) -> Result<(), Error> {
-    let path = "README.md";
-    let mut readme = String::from_utf8_lossy(&fs::read(path)?).to_string();
+/// Updates the benchmark table of the readme. `previous` holds the timings that the `Speedup`
+/// column compares against.
+pub fn update(
+    timings: Timings,
+    previous: &Timings,
+    config: &ReadmeConfig,
+) -> Result<(), TemplateError> {
+    let to_error = |e: Error| e.into_template_error(&config.path);
+    let mut readme = String::from_utf8_lossy(
+        &fs::read(&config.path)
+            .map_err(Error::from)
+            .map_err(to_error)?,
+    )
+    .to_string();
     let total_millis = timings.total_millis();
-    update_content(&mut readme, timings, total_millis)?;
-    fs::write(path, &readme)?;
+
+    update_content(&mut readme, timings, total_millis, previous, config).map_err(to_error)?;
+    fs::write(&config.path, &readme)
+        .map_err(Error::from)
+        .map_err(to_error)?;
     Ok(())
 }
 
-#[cfg(feature = "test_lib")]
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use super::{update_content, MARKER};
-    use crate::{day, template::timings::Timing, template::timings::Timings};
+    use super::{format_bar, update_content, MARKER};
+    use crate::{
+        puzzle,
+        template::config::{BarStyle, Column, ReadmeConfig, SortOrder},
+        template::stats::MemoryStats,
+        template::timings::Timing,
+        template::timings::Timings,
+    };
 
     fn get_mock_timings() -> Timings {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    id: puzzle!(2023, 1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    id: puzzle!(2023, 2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    id: puzzle!(2023, 4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: Some("50ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 9e+10,
                 },
             ],
@@ -130,28 +335,63 @@ mod tests {
     #[should_panic]
     fn errors_if_marker_not_present() {
         let mut s = "# readme".to_string();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     #[should_panic]
     fn errors_if_too_many_markers_present() {
         let mut s = format!("{} {} {}", MARKER, MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
     }
 
     #[test]
     fn updates_empty_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        assert_eq!(s.contains("## Benchmarks"), true);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
+        assert!(s.contains("## Benchmarks"));
     }
 
     #[test]
     fn updates_existing_benchmarks() {
         let mut s = format!("foo\nbar\n{}{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
         assert_eq!(s.matches(MARKER).collect::<Vec<&str>>().len(), 2);
         assert_eq!(s.matches("## Benchmarks").collect::<Vec<&str>>().len(), 1);
     }
@@ -159,7 +399,14 @@ mod tests {
     #[test]
     fn format_benchmarks() {
         let mut s = format!("foo\nbar\n{}\n{}\nbaz", MARKER, MARKER);
-        update_content(&mut s, get_mock_timings(), 190.0).unwrap();
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
         let expected = [
             "foo",
             "bar",
@@ -167,10 +414,10 @@ mod tests {
             "## Benchmarks",
             "",
             "| Day | Part 1 | Part 2 |",
-            "| :---: | :---: | :---:  |",
-            "| [Day 1](./src/bin/01.rs) | `10ms` | `20ms` |",
-            "| [Day 2](./src/bin/02.rs) | `30ms` | `40ms` |",
-            "| [Day 4](./src/bin/04.rs) | `40ms` | `50ms` |",
+            "| :---: | :---: | :---: |",
+            "| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `30ms` | `40ms` |",
+            "| [Day 4](./src/bin/2023-04.rs) | `40ms` | `50ms` |",
             "",
             "**Total: 190.00ms**",
             "<!--- benchmarking table --->",
@@ -179,4 +426,130 @@ mod tests {
         .join("\n");
         assert_eq!(s, expected);
     }
+
+    #[test]
+    fn format_memory_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[1].part_2_memory = Some(MemoryStats {
+            total_bytes: 4096,
+            total_blocks: 4,
+            max_bytes: 1536,
+        });
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            190.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Part 1 | Part 2 | Peak memory |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `10ms` | `20ms` | `-` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | `30ms` | `40ms` | `1.5 KiB` |"));
+    }
+
+    #[test]
+    fn format_parse_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[1].parse = Some("5ms".into());
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            195.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| Day | Parse | Part 1 | Part 2 |"));
+        assert!(s.contains("| [Day 1](./src/bin/2023-01.rs) | `-` | `10ms` | `20ms` |"));
+        assert!(s.contains("| [Day 2](./src/bin/2023-02.rs) | `5ms` | `30ms` | `40ms` |"));
+        assert!(s.contains("**Total: 195.00ms**"));
+    }
+
+    #[test]
+    fn format_multi_year_benchmarks() {
+        let mut timings = get_mock_timings();
+        timings.data[0].id = puzzle!(2022, 25);
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            timings,
+            190.0,
+            &Timings::default(),
+            &ReadmeConfig::default(),
+        )
+        .unwrap();
+        assert!(s.contains("| [2022 Day 25](./src/bin/2022-25.rs) | `10ms` | `20ms` |"));
+        assert!(s.contains("| [2023 Day 2](./src/bin/2023-02.rs) | `30ms` | `40ms` |"));
+    }
+
+    #[test]
+    fn format_configured_benchmarks() {
+        let config = ReadmeConfig {
+            title: "### Timings".into(),
+            columns: Some(vec![Column::Part2, Column::Speedup]),
+            sort: SortOrder::Duration,
+            bars: Some(BarStyle::Ascii),
+            bar_width: 9,
+            footer: "Took {total} in total.".into(),
+            ..ReadmeConfig::default()
+        };
+
+        let mut previous = get_mock_timings();
+        previous.data.truncate(1);
+        previous.data[0].total_nanos = 6e+10;
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(&mut s, get_mock_timings(), 190.0, &previous, &config).unwrap();
+        let expected = [
+            "<!--- benchmarking table --->",
+            "### Timings",
+            "",
+            "| Day | Part 2 | Speedup | Cost |",
+            "| :---: | :---: | :---: | :---: |",
+            "| [Day 4](./src/bin/2023-04.rs) | `50ms` | `-` | `#########` |",
+            "| [Day 2](./src/bin/2023-02.rs) | `40ms` | `-` | `#######` |",
+            "| [Day 1](./src/bin/2023-01.rs) | `20ms` | `2.00×` | `###` |",
+            "",
+            "Took 190.00ms in total.",
+            "<!--- benchmarking table --->",
+        ]
+        .join("\n");
+        assert_eq!(s, expected);
+    }
+
+    #[test]
+    fn omits_empty_footer() {
+        let config = ReadmeConfig {
+            footer: String::new(),
+            ..ReadmeConfig::default()
+        };
+
+        let mut s = format!("{}\n{}", MARKER, MARKER);
+        update_content(
+            &mut s,
+            get_mock_timings(),
+            190.0,
+            &Timings::default(),
+            &config,
+        )
+        .unwrap();
+        assert!(!s.contains("Total"));
+        assert!(s.ends_with("| `50ms` |\n<!--- benchmarking table --->"));
+    }
+
+    #[test]
+    fn formats_bars() {
+        assert_eq!(format_bar(1.0, 4, BarStyle::Ascii), "####");
+        assert_eq!(format_bar(0.01, 4, BarStyle::Ascii), "#");
+        assert_eq!(format_bar(0.0, 4, BarStyle::Ascii), "");
+        assert_eq!(format_bar(1.0, 2, BarStyle::Unicode), "██");
+        assert_eq!(format_bar(0.5625, 2, BarStyle::Unicode), "█▏");
+        assert_eq!(format_bar(0.001, 2, BarStyle::Unicode), "▏");
+    }
 }
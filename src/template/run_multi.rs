@@ -1,256 +1,334 @@
-use std::{collections::HashSet, io};
+use std::{
+    collections::{BTreeMap, BTreeSet, HashMap, HashSet},
+    fs,
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        mpsc,
+    },
+    thread,
+};
+
+use tinyjson::JsonValue;
 
-use crate::template::{Day, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{all_puzzles, PuzzleId, Year, ANSI_BOLD, ANSI_ITALIC, ANSI_RESET};
 
 use super::{
-    all_days,
-    timings::{Timing, Timings},
+    answers::Answers,
+    error::TemplateError,
+    get_data_path,
+    output::{self, errln, outln},
+    runner::{record, run_solution, DayResult, OutputFormat, RunOptions, Solution},
+    timings::Timings,
 };
 
-pub fn run_multi(days_to_run: &HashSet<Day>, is_release: bool, is_timed: bool) -> Option<Timings> {
-    let mut timings: Vec<Timing> = Vec::with_capacity(days_to_run.len());
-
-    let mut need_space = false;
-
-    // NOTE: use non-duplicate, sorted day values.
-    all_days()
-        .filter(|day| days_to_run.contains(day))
-        .for_each(|day| {
-            if need_space {
-                println!();
-            }
-            need_space = true;
+/// Returns every puzzle of a year. If no year is given, returns every puzzle of the years
+/// that have solutions in the workspace, or of the configured year if there are none.
+pub fn get_puzzles(solutions: &[Solution], year: Option<Year>) -> HashSet<PuzzleId> {
+    let mut years: HashSet<Year> = match year {
+        Some(year) => HashSet::from([year]),
+        None => solutions.iter().map(|s| s.id.year).collect(),
+    };
 
-            println!("{ANSI_BOLD}Day {day}{ANSI_RESET}");
-            println!("------");
+    if years.is_empty() {
+        years.extend(Year::configured());
+    }
 
-            let output = child_commands::run_solution(day, is_timed, is_release).unwrap();
+    years.into_iter().flat_map(all_puzzles).collect()
+}
 
-            if output.is_empty() {
-                println!("Not solved.");
-            } else {
-                let val = child_commands::parse_exec_time(&output, day);
-                timings.push(val);
+/// Run the solutions for a set of puzzles in-process, in order, then print a summary.
+/// Puzzles that have not been scaffolded yet are skipped.
+pub fn run_multi(
+    solutions: &[Solution],
+    puzzles_to_run: &HashSet<PuzzleId>,
+    options: &RunOptions,
+) -> Vec<DayResult> {
+    let results = run_days(solutions, puzzles_to_run, options);
+    let is_multi_year = is_multi_year(puzzles_to_run);
+
+    match options.format {
+        OutputFormat::Text => {
+            print_summary(&results, is_multi_year);
+
+            if options.timed {
+                let total_millis = Timings::from(results.as_slice()).total_millis();
+                println!(
+                    "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
+                );
             }
-        });
-
-    if is_timed {
-        let timings = Timings { data: timings };
-        let total_millis = timings.total_millis();
-        println!(
-            "\n{ANSI_BOLD}Total (Run):{ANSI_RESET} {ANSI_ITALIC}{total_millis:.2}ms{ANSI_RESET}"
-        );
-        Some(timings)
-    } else {
-        None
+        }
+        OutputFormat::Ndjson => {
+            let mut event: HashMap<String, JsonValue> = HashMap::new();
+            event.insert("type".into(), JsonValue::String("summary".into()));
+            event.insert("days".into(), summarize(&results));
+            println!("{}", JsonValue::Object(event).stringify().unwrap());
+        }
+        OutputFormat::Json => {
+            let days = JsonValue::Array(results.iter().map(JsonValue::from).collect());
+            let document = JsonValue::Object([("days".to_string(), days)].into());
+            println!("{}", document.format().unwrap());
+        }
     }
-}
 
-#[derive(Debug)]
-pub enum Error {
-    BrokenPipe,
-    IO(io::Error),
+    results
 }
 
-impl From<std::io::Error> for Error {
-    fn from(e: std::io::Error) -> Self {
-        Error::IO(e)
+/// Run the solutions for a set of puzzles in-process, in order, without printing a summary.
+/// Puzzles that have not been scaffolded yet are skipped.
+pub(crate) fn run_days(
+    solutions: &[Solution],
+    puzzles_to_run: &HashSet<PuzzleId>,
+    options: &RunOptions,
+) -> Vec<DayResult> {
+    // NOTE: answers are only compared against, run the days without them if they are unreadable.
+    let answers = Answers::read_from_file().unwrap_or_else(|e| {
+        eprintln!("Not checking answers: {e}");
+        Answers::default()
+    });
+
+    let is_multi_year = is_multi_year(puzzles_to_run);
+
+    // NOTE: use sorted puzzle values.
+    let puzzles: Vec<PuzzleId> = puzzles_to_run
+        .iter()
+        .copied()
+        .collect::<BTreeSet<_>>()
+        .into_iter()
+        .collect();
+
+    let run = |index: usize, id: PuzzleId| {
+        run_day(solutions, id, &answers, options, index > 0, is_multi_year)
+    };
+
+    let jobs = options.jobs().clamp(1, puzzles.len().max(1));
+
+    if jobs == 1 {
+        puzzles
+            .iter()
+            .enumerate()
+            .filter_map(|(index, id)| run(index, *id))
+            .collect()
+    } else {
+        run_parallel(&puzzles, jobs, run)
     }
 }
 
-#[must_use]
-pub fn get_path_for_bin(day: Day) -> String {
-    format!("./src/bin/{day}.rs")
+/// Whether the puzzles span more than one year. Headers are only prefixed with the year if so.
+fn is_multi_year(puzzles: &HashSet<PuzzleId>) -> bool {
+    puzzles
+        .iter()
+        .map(|id| id.year)
+        .collect::<HashSet<_>>()
+        .len()
+        > 1
 }
 
-/// All solutions live in isolated binaries.
-/// This module encapsulates interaction with these binaries, both invoking them as well as parsing the timing output.
-pub mod child_commands {
-    use super::{get_path_for_bin, Error};
-    use crate::template::Day;
-    use std::{
-        io::{BufRead, BufReader},
-        path::Path,
-        process::{Command, Stdio},
-        thread,
-    };
+/// Runs the solution of a single day, printing a header before it.
+/// Returns `None` if the day has not been scaffolded yet.
+fn run_day(
+    solutions: &[Solution],
+    id: PuzzleId,
+    answers: &Answers,
+    options: &RunOptions,
+    need_space: bool,
+    is_multi_year: bool,
+) -> Option<DayResult> {
+    if options.is_text() {
+        if need_space {
+            outln!();
+        }
 
-    /// Run the solution bin for a given day
-    pub fn run_solution(day: Day, is_timed: bool, is_release: bool) -> Result<Vec<String>, Error> {
-        // skip command invocation for days that have not been scaffolded yet.
-        if !Path::new(&get_path_for_bin(day)).exists() {
-            return Ok(vec![]);
+        if is_multi_year {
+            outln!("{ANSI_BOLD}{} Day {}{ANSI_RESET}", id.year, id.day);
+        } else {
+            outln!("{ANSI_BOLD}Day {}{ANSI_RESET}", id.day);
         }
+        outln!("------");
+    }
 
-        let day_padded = day.to_string();
-        let mut args = vec!["run", "--quiet", "--bin", &day_padded];
+    let Some(solution) = solutions.iter().find(|s| s.id == id) else {
+        if options.is_text() {
+            outln!("Not solved.");
+        }
+        return None;
+    };
 
-        if is_release {
-            args.push("--release");
+    let result = match read_input(id) {
+        Ok(input) => {
+            let mut result = run_solution(solution, input, options);
+            result.check_answers(answers);
+            result
         }
+        Err(e) => DayResult {
+            error: Some(e.to_string()),
+            ..DayResult::new(id)
+        },
+    };
 
-        if is_timed {
-            // mirror `--time` flag to child invocations.
-            args.push("--");
-            args.push("--time");
+    if let Some(e) = &result.error {
+        match options.format {
+            OutputFormat::Text => errln!("Error: {e}"),
+            OutputFormat::Ndjson => {
+                let mut event = record("error", id);
+                event.insert("error".into(), JsonValue::String(e.clone()));
+                outln!("{}", JsonValue::Object(event).stringify().unwrap());
+            }
+            OutputFormat::Json => {}
         }
+    }
 
-        // spawn child command with piped stdout/stderr.
-        // forward output to stdout/stderr while grabbing stdout lines.
+    Some(result)
+}
 
-        let mut cmd = Command::new("cargo")
-            .args(&args)
-            .stdout(Stdio::piped())
-            .stderr(Stdio::piped())
-            .spawn()?;
+/// Runs days on `jobs` worker threads. The output of each day is captured and printed once
+/// all days before it are printed, so the output reads the same as a sequential run.
+fn run_parallel(
+    puzzles: &[PuzzleId],
+    jobs: usize,
+    run: impl Fn(usize, PuzzleId) -> Option<DayResult> + Sync,
+) -> Vec<DayResult> {
+    let next_index = AtomicUsize::new(0);
+    let (sender, receiver) = mpsc::channel();
+
+    thread::scope(|scope| {
+        for _ in 0..jobs {
+            let (sender, next_index, run) = (sender.clone(), &next_index, &run);
+            scope.spawn(move || loop {
+                let index = next_index.fetch_add(1, Ordering::Relaxed);
+                let Some(&id) = puzzles.get(index) else {
+                    break;
+                };
 
-        let stdout = BufReader::new(cmd.stdout.take().ok_or(super::Error::BrokenPipe)?);
-        let stderr = BufReader::new(cmd.stderr.take().ok_or(super::Error::BrokenPipe)?);
+                let (result, captured) = output::capture(|| run(index, id));
+                if sender.send((index, result, captured)).is_err() {
+                    break;
+                }
+            });
+        }
+        drop(sender);
 
-        let mut output = vec![];
+        let mut results = Vec::with_capacity(puzzles.len());
+        let mut pending = BTreeMap::new();
+        let mut next_to_print = 0;
 
-        let thread = thread::spawn(move || {
-            stderr.lines().for_each(|line| {
-                eprintln!("{}", line.unwrap());
-            });
-        });
+        for (index, result, captured) in receiver {
+            pending.insert(index, (result, captured));
 
-        for line in stdout.lines() {
-            let line = line.unwrap();
-            println!("{line}");
-            output.push(line);
+            while let Some((result, captured)) = pending.remove(&next_to_print) {
+                captured.print();
+                results.extend(result);
+                next_to_print += 1;
+            }
         }
 
-        thread.join().unwrap();
-        cmd.wait()?;
+        results
+    })
+}
 
-        Ok(output)
+/// Prints the status of every part that was run.
+fn print_summary(results: &[DayResult], is_multi_year: bool) {
+    if results.is_empty() {
+        return;
     }
 
-    pub fn parse_exec_time(output: &[String], day: Day) -> super::Timing {
-        let mut timings = super::Timing {
-            day,
-            part_1: None,
-            part_2: None,
-            total_nanos: 0_f64,
+    println!();
+    println!("{ANSI_BOLD}Summary{ANSI_RESET}");
+    println!("------");
+
+    for result in results {
+        let label = if is_multi_year {
+            format!("{} Day {}", result.id.year, result.id.day)
+        } else {
+            format!("Day {}", result.id.day)
         };
 
-        output
-            .iter()
-            .filter_map(|l| {
-                if !l.contains(" samples)") {
-                    return None;
-                }
+        if let Some(error) = &result.error {
+            println!("{label}: ✖ {error}");
+            continue;
+        }
 
-                let Some((timing_str, nanos)) = parse_time(l) else {
-                    eprintln!("Could not parse timings from line: {l}");
-                    return None;
-                };
+        for part in &result.parts {
+            println!("{label} Part {}: {}", part.part, part.status);
+        }
+    }
 
-                let part = l.split(':').next()?;
-                Some((part, timing_str, nanos))
-            })
-            .for_each(|(part, timing_str, nanos)| {
-                if part.contains("Part 1") {
-                    timings.part_1 = Some(timing_str.into());
-                } else if part.contains("Part 2") {
-                    timings.part_2 = Some(timing_str.into());
-                }
+    let parts = results.iter().flat_map(|r| &r.parts);
+    let solved = parts.clone().filter(|p| p.status.is_solved()).count();
+    println!("{solved}/{} parts solved.", parts.count());
+}
 
-                timings.total_nanos += nanos;
-            });
+/// Lists the status of every part that was run as JSON.
+fn summarize(results: &[DayResult]) -> JsonValue {
+    let days = results
+        .iter()
+        .map(|result| {
+            let mut map = record("day", result.id);
+            map.remove("type");
+            map.insert(
+                "error".into(),
+                result
+                    .error
+                    .clone()
+                    .map_or(JsonValue::Null, JsonValue::String),
+            );
+            map.insert(
+                "parts".into(),
+                JsonValue::Array(
+                    result
+                        .parts
+                        .iter()
+                        .map(|part| {
+                            let mut map: HashMap<String, JsonValue> = HashMap::new();
+                            map.insert("part".into(), JsonValue::Number(f64::from(part.part)));
+                            map.insert(
+                                "status".into(),
+                                JsonValue::String(part.status.as_str().into()),
+                            );
+                            JsonValue::Object(map)
+                        })
+                        .collect(),
+                ),
+            );
+            JsonValue::Object(map)
+        })
+        .collect();
 
-        timings
-    }
+    JsonValue::Array(days)
+}
 
-    fn parse_to_float(s: &str, postfix: &str) -> Option<f64> {
-        s.split(postfix).next()?.parse().ok()
+/// Reads the input of a puzzle, failing if it is missing or empty.
+pub(crate) fn read_input(id: PuzzleId) -> Result<String, TemplateError> {
+    let path = get_data_path("inputs", id);
+    match fs::read_to_string(&path) {
+        Ok(input) if input.trim().is_empty() => Err(TemplateError::EmptyInput { id, path }),
+        Ok(input) => Ok(input),
+        Err(source) => Err(TemplateError::MissingInput { id, path, source }),
     }
+}
 
-    fn parse_time(line: &str) -> Option<(&str, f64)> {
-        // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
-        let str_timing = line
-            .split(" samples)")
-            .next()?
-            .split('(')
-            .last()?
-            .split('@')
-            .next()?
-            .trim();
-
-        let parsed_timing = match str_timing {
-            s if s.contains("ns") => s.split("ns").next()?.parse::<f64>().ok(),
-            s if s.contains("µs") => parse_to_float(s, "µs").map(|x| x * 1000_f64),
-            s if s.contains("ms") => parse_to_float(s, "ms").map(|x| x * 1_000_000_f64),
-            s => parse_to_float(s, "s").map(|x| x * 1_000_000_000_f64),
-        }?;
-
-        Some((str_timing, parsed_timing))
-    }
+/* -------------------------------------------------------------------------- */
 
-    /// copied from: https://github.com/rust-lang/rust/blob/1.64.0/library/std/src/macros.rs#L328-L333
-    #[cfg(feature = "test_lib")]
-    macro_rules! assert_approx_eq {
-        ($a:expr, $b:expr) => {{
-            let (a, b) = (&$a, &$b);
-            assert!(
-                (*a - *b).abs() < 1.0e-6,
-                "{} is not approximately equal to {}",
-                *a,
-                *b
-            );
-        }};
-    }
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{thread, time::Duration};
 
-    #[cfg(feature = "test_lib")]
-    mod tests {
-        use super::parse_exec_time;
-
-        use crate::day;
-
-        #[test]
-        fn parses_execution_times() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: 0 (74.13ns @ 100000 samples)".into(),
-                    "Part 2: 10 (74.13ms @ 99999 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 74130074.13_f64);
-            assert_eq!(res.part_1.unwrap(), "74.13ns");
-            assert_eq!(res.part_2.unwrap(), "74.13ms");
-        }
+    use super::run_parallel;
+    use crate::{
+        template::{all_puzzles, runner::DayResult, PuzzleId},
+        year,
+    };
 
-        #[test]
-        fn parses_with_patterns_in_input() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: @ @ @ ( ) ms (2s @ 5 samples)".into(),
-                    "Part 2: 10s (100ms @ 1 samples)".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 2100000000_f64);
-            assert_eq!(res.part_1.unwrap(), "2s");
-            assert_eq!(res.part_2.unwrap(), "100ms");
-        }
+    #[test]
+    fn runs_days_in_parallel_in_order() {
+        let puzzles: Vec<PuzzleId> = all_puzzles(year!(2023)).take(6).collect();
 
-        #[test]
-        fn parses_missing_parts() {
-            let res = parse_exec_time(
-                &[
-                    "Part 1: ✖        ".into(),
-                    "Part 2: ✖        ".into(),
-                    "".into(),
-                ],
-                day!(1),
-            );
-            assert_approx_eq!(res.total_nanos, 0_f64);
-            assert_eq!(res.part_1.is_none(), true);
-            assert_eq!(res.part_2.is_none(), true);
-        }
+        let results = run_parallel(&puzzles, 3, |index, id| {
+            // NOTE: later days finish first.
+            thread::sleep(Duration::from_millis(10 * (6 - index as u64)));
+            (id.day.into_inner() != 4).then(|| DayResult::new(id))
+        });
+
+        let days: Vec<u8> = results.iter().map(|r| r.id.day.into_inner()).collect();
+        assert_eq!(days, vec![1, 2, 3, 5, 6]);
     }
 }
@@ -1,72 +1,696 @@
 /// Encapsulates code that interacts with solution functions.
+use std::any::Any;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::env;
 use std::fmt::Display;
 use std::hint::black_box;
-use std::io::{stdout, Write};
-use std::process::Output;
+use std::panic::{self, AssertUnwindSafe};
+use std::str::FromStr;
+#[cfg(feature = "dhat-heap")]
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::mpsc::{self, RecvTimeoutError, Sender};
+use std::thread;
 use std::time::{Duration, Instant};
-use std::{cmp, env, process};
 
+use tinyjson::JsonValue;
+
+use crate::template::answers::Answers;
+use crate::template::output::{self, out, outln};
+use crate::template::stats::{BenchStats, MemoryStats};
+use crate::template::submissions::{Submissions, Verdict};
 use crate::template::ANSI_BOLD;
-use crate::template::{aoc_cli, Day, ANSI_ITALIC, ANSI_RESET};
+use crate::template::{
+    aoc_client, config, error::TemplateError, run_multi::read_input, PuzzleId, ANSI_ITALIC,
+    ANSI_RESET,
+};
+
+/// A solution for a single day that can be run in-process, e.g. by `cargo all` and `cargo time`.
+/// Created by the [`solution!`](crate::solution) macro as the constant `SOLUTION`.
+pub struct Solution {
+    pub id: PuzzleId,
+    pub run: fn(&str, &RunOptions) -> DayResult,
+    /// Time limit of a single part, overrides the global default.
+    pub timeout: Option<Duration>,
+}
+
+/// Time limit of a single part if neither the solution, `--timeout` nor `aoc.toml` set one.
+pub const DEFAULT_TIMEOUT: Duration = Duration::from_secs(60);
+
+/// Total time spent benching a single part if neither `--bench-budget` nor `aoc.toml` set one.
+pub const DEFAULT_BENCH_BUDGET: Duration = Duration::from_secs(5);
+
+/// Stack size of the thread solutions run on, matches the main thread on most platforms.
+const STACK_SIZE: usize = 8 * 1024 * 1024;
+
+/// Options that control how the parts of a solution are run.
+#[derive(Clone, Debug, Default)]
+pub struct RunOptions {
+    /// Bench each part instead of running it once.
+    pub timed: bool,
+    /// Submit the result of this part after running it.
+    pub submit: Option<u8>,
+    /// How results are written to stdout.
+    pub format: OutputFormat,
+    /// Submit even if the answer is known to be wrong or looks invalid.
+    pub force: bool,
+    /// Time limit of a single part for solutions that do not set their own.
+    pub timeout: Option<Duration>,
+    /// Total time spent benching a single part.
+    pub bench_budget: Option<Duration>,
+    /// Number of days that run concurrently in multi-day runs, one unless `--jobs` is passed.
+    pub jobs: Option<usize>,
+}
+
+impl RunOptions {
+    /// Reads the options from the command-line arguments passed to a solution binary.
+    pub fn from_args() -> Result<Self, TemplateError> {
+        let args: Vec<String> = env::args().collect();
+
+        let submit = args
+            .iter()
+            .position(|x| x == "--submit")
+            .map(|index| {
+                args.get(index + 1)
+                    .and_then(|x| x.parse::<u8>().ok())
+                    .ok_or_else(|| {
+                        TemplateError::Usage(
+                            "unexpected command-line input. Format: cargo solve 1 --submit 1"
+                                .into(),
+                        )
+                    })
+            })
+            .transpose()?;
+
+        let format = args
+            .iter()
+            .position(|x| x == "--format")
+            .map(|index| match args.get(index + 1) {
+                Some(x) => x.parse::<OutputFormat>().map_err(TemplateError::Usage),
+                None => Err(TemplateError::Usage(
+                    "unexpected command-line input. Format: cargo solve 1 --format json".into(),
+                )),
+            })
+            .transpose()?;
+
+        let seconds = |flag: &str| {
+            args.iter()
+                .position(|x| x == flag)
+                .map(|index| match args.get(index + 1) {
+                    Some(x) => parse_seconds(x).map_err(TemplateError::Usage),
+                    None => Err(TemplateError::Usage(format!(
+                        "unexpected command-line input. Format: cargo solve 1 {flag} 30"
+                    ))),
+                })
+                .transpose()
+        };
+
+        Ok(Self {
+            timed: args.iter().any(|x| x == "--time"),
+            submit,
+            format: format.unwrap_or(config::get().run.format),
+            force: args.iter().any(|x| x == "--force"),
+            timeout: seconds("--timeout")?,
+            bench_budget: seconds("--bench-budget")?,
+            jobs: None,
+        })
+    }
+
+    /// Whether human-readable output should be printed.
+    pub fn is_text(&self) -> bool {
+        self.format == OutputFormat::Text
+    }
+
+    fn bench_budget(&self) -> Duration {
+        self.bench_budget.unwrap_or(config::get().bench.budget)
+    }
+
+    pub(crate) fn jobs(&self) -> usize {
+        // NOTE: only a single `dhat` profiler can run at a time.
+        if cfg!(feature = "dhat-heap") {
+            return 1;
+        }
+
+        // NOTE: days only run concurrently if asked to, as the output of solutions interleaves.
+        self.jobs.unwrap_or(1)
+    }
+}
+
+/// Parses a number of seconds, e.g. `30` or `0.5`.
+pub fn parse_seconds(s: &str) -> Result<Duration, String> {
+    s.parse::<f64>()
+        .ok()
+        .and_then(|secs| Duration::try_from_secs_f64(secs).ok())
+        .ok_or_else(|| format!("invalid number of seconds `{s}`."))
+}
+
+/// Output format of `solve`, `all` and `time`.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum OutputFormat {
+    /// Human-readable output.
+    #[default]
+    Text,
+    /// A single JSON document, written once all solutions have run.
+    Json,
+    /// One JSON object per line, written as soon as a result is available.
+    Ndjson,
+}
+
+impl FromStr for OutputFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Self::Text),
+            "json" => Ok(Self::Json),
+            "ndjson" => Ok(Self::Ndjson),
+            x => Err(format!(
+                "unknown output format `{x}`, expecting one of `text`, `json` or `ndjson`"
+            )),
+        }
+    }
+}
+
+/// How running a single part of a solution turned out.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum PartStatus {
+    /// The part returned an answer.
+    Solved,
+    /// The part returned `None`.
+    NoResult,
+    /// The part panicked, holds the panic message.
+    Panicked(String),
+    /// The part did not finish within its time limit.
+    TimedOut,
+    /// The part returned an answer that differs from the accepted one.
+    WrongAnswer { expected: String },
+}
+
+impl PartStatus {
+    pub fn is_solved(&self) -> bool {
+        *self == PartStatus::Solved
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PartStatus::Solved => "solved",
+            PartStatus::NoResult => "no_result",
+            PartStatus::Panicked(_) => "panicked",
+            PartStatus::TimedOut => "timed_out",
+            PartStatus::WrongAnswer { .. } => "wrong_answer",
+        }
+    }
+}
+
+impl Display for PartStatus {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            PartStatus::Solved => write!(f, "✔ solved"),
+            PartStatus::NoResult => write!(f, "✖ no result"),
+            PartStatus::Panicked(message) => write!(f, "✖ panicked: {message}"),
+            PartStatus::TimedOut => write!(f, "✖ timed out"),
+            PartStatus::WrongAnswer { expected } => {
+                write!(f, "✖ wrong answer (expected {expected})")
+            }
+        }
+    }
+}
+
+/// The outcome of running a single part of a solution.
+#[derive(Clone, Debug)]
+pub struct PartResult {
+    pub part: u8,
+    pub answer: Option<String>,
+    pub stats: BenchStats,
+    /// Heap usage of the first run, only measured with the `dhat-heap` feature.
+    pub memory: Option<MemoryStats>,
+    pub status: PartStatus,
+}
+
+/// The outcome of running a solution for a single day.
+#[derive(Clone, Debug)]
+pub struct DayResult {
+    pub id: PuzzleId,
+    pub parse: Option<BenchStats>,
+    pub parts: Vec<PartResult>,
+    /// Set if the solution could not be run, e.g. because the input file is missing.
+    pub error: Option<String>,
+    /// Set if a step exceeded its time limit. Its thread keeps running in the background and
+    /// skews the timings of the days that run after it.
+    pub timed_out: bool,
+}
+
+impl DayResult {
+    pub fn new(id: PuzzleId) -> Self {
+        Self {
+            id,
+            parse: None,
+            parts: vec![],
+            error: None,
+            timed_out: false,
+        }
+    }
+
+    /// Marks solved parts whose answer differs from the accepted answer as wrong.
+    pub(crate) fn check_answers(&mut self, answers: &Answers) {
+        for part in &mut self.parts {
+            if !part.status.is_solved() {
+                continue;
+            }
+
+            if let (Some(expected), Some(answer)) =
+                (answers.get(self.id, part.part), part.answer.as_deref())
+            {
+                if expected != answer {
+                    part.status = PartStatus::WrongAnswer {
+                        expected: expected.into(),
+                    };
+                }
+            }
+        }
+    }
+}
 
-pub fn run_part<I: Clone, T: Display>(func: impl Fn(I) -> Option<T>, input: I, day: Day, part: u8) {
+pub fn run_part<I: Clone, T: Display>(
+    func: impl Fn(I) -> Option<T>,
+    input: I,
+    id: PuzzleId,
+    part: u8,
+    options: &RunOptions,
+) -> PartResult {
     let part_str = format!("Part {part}");
 
-    let (result, duration, samples) =
-        run_timed(func, input, |result| print_result(result, &part_str, ""));
+    // the watchdog of `run_solution` gave up on this solution, skip the remaining parts.
+    if !report(Progress::Started(Some(part))) {
+        return PartResult {
+            part,
+            answer: None,
+            stats: BenchStats::from_samples(&[Duration::ZERO]).unwrap(),
+            memory: None,
+            status: PartStatus::TimedOut,
+        };
+    }
+
+    // NOTE: a panicking part should not take down the other parts of a multi-day run.
+    let func = |input: I| panic::catch_unwind(AssertUnwindSafe(|| func(input)));
+    let is_watched = Cell::new(true);
+
+    let (result, stats, memory) = run_timed(
+        func,
+        input,
+        options,
+        |result| {
+            is_watched.set(report(Progress::Ran));
+            if options.is_text() && is_watched.get() {
+                if let Ok(result) = result {
+                    print_result(result, &part_str, "");
+                }
+            }
+        },
+        |result| result.is_ok() && is_watched.get(),
+    );
+
+    let (answer, status) = match result {
+        Ok(Some(result)) => (Some(result.to_string()), PartStatus::Solved),
+        Ok(None) => (None, PartStatus::NoResult),
+        Err(payload) => (None, PartStatus::Panicked(panic_message(&*payload))),
+    };
+
+    let part_result = PartResult {
+        part,
+        answer,
+        stats,
+        memory,
+        status,
+    };
+
+    // the part finished after timing out, its result was already reported as a timeout.
+    if !is_watched.get() {
+        return part_result;
+    }
+
+    if options.is_text() {
+        let stats = &part_result.stats;
+        let duration_str = format_duration(&stats.mean, stats.samples);
+        match &part_result.status {
+            PartStatus::Panicked(message) => {
+                outln!("{part_str}: ✖ panicked: {message}{duration_str}");
+            }
+            _ => print_result(&part_result.answer, &part_str, &duration_str),
+        }
+
+        if stats.samples > 1 {
+            outln!("{part_str} stats: {}", format_stats(stats));
+        }
+
+        if let Some(memory) = &part_result.memory {
+            outln!("{part_str} memory: {memory}");
+        }
+    }
 
-    print_result(&result, &part_str, &format_duration(&duration, samples));
+    if options.format == OutputFormat::Ndjson {
+        print_part_event(id, &part_result);
+    }
+
+    if let Some(answer) = &part_result.answer {
+        if options.submit == Some(part) {
+            submit_result(answer, id, part, options);
+        }
+    }
+
+    report(Progress::Part(part_result.clone()));
+
+    part_result
+}
 
-    if let Some(result) = result {
-        submit_result(result, day, part);
+fn print_part_event(id: PuzzleId, part_result: &PartResult) {
+    let mut event = record("part", id);
+    if let JsonValue::Object(map) = JsonValue::from(part_result) {
+        event.extend(map);
     }
+    outln!("{}", JsonValue::Object(event).stringify().unwrap());
 }
 
-/// Run a solution part. The behavior differs depending on whether we are running a release or debug build:
-///  1. in debug, the function is executed once.
-///  2. in release, the function is benched (approx. 1 second of execution time or 10 samples, whatever take longer.)
+/// Run the parse step of a solution, timing it like a solution part.
+/// Returns the parsed input and the timing statistics.
+pub fn run_parse<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    id: PuzzleId,
+    options: &RunOptions,
+) -> (T, BenchStats) {
+    let label = "Parse";
+
+    report(Progress::Started(None));
+    let is_watched = Cell::new(true);
+
+    let (result, stats, _) = run_timed(
+        func,
+        input,
+        options,
+        |_| {
+            is_watched.set(report(Progress::Ran));
+            if options.is_text() && is_watched.get() {
+                out!("{label}: ✔");
+            }
+        },
+        |_| is_watched.get(),
+    );
+
+    if !is_watched.get() {
+        return (result, stats);
+    }
+
+    if options.is_text() {
+        out!("\r");
+        outln!("{label}: ✔{}", format_duration(&stats.mean, stats.samples));
+
+        if stats.samples > 1 {
+            outln!("{label} stats: {}", format_stats(&stats));
+        }
+    }
+
+    if options.format == OutputFormat::Ndjson {
+        let mut event = record("parse", id);
+        event.insert("stats".into(), JsonValue::from(&stats));
+        outln!("{}", JsonValue::Object(event).stringify().unwrap());
+    }
+
+    report(Progress::Parse(stats.clone()));
+
+    (result, stats)
+}
+
+/// Entry point of a solution binary: reads the input and options, then runs the solution.
+pub fn run_main(solution: &Solution) {
+    let options = RunOptions::from_args().unwrap_or_else(|e| e.exit());
+    let input = read_input(solution.id).unwrap_or_else(|e| e.exit());
+    let result = run_solution(solution, input, &options);
+
+    if options.format == OutputFormat::Json {
+        outln!("{}", JsonValue::from(&result).format().unwrap());
+    }
+
+    if let Some(error) = result.error {
+        TemplateError::SolutionFailed(error).exit();
+    }
+}
+
+/// Progress of a solution, reported by the runner to the watchdog of [`run_solution`].
+#[derive(Debug)]
+enum Progress {
+    /// The parse step (`None`) or a part started running.
+    Started(Option<u8>),
+    /// The first run of the current step finished, benching may follow.
+    Ran,
+    Parse(BenchStats),
+    Part(PartResult),
+    Done(Result<DayResult, String>),
+}
+
+thread_local! {
+    static PROGRESS: RefCell<Option<Sender<Progress>>> = const { RefCell::new(None) };
+}
+
+/// Reports progress to the watchdog of the current solution, if there is one.
+/// Returns false if the watchdog gave up on the solution.
+fn report(progress: Progress) -> bool {
+    PROGRESS.with(|sender| match &*sender.borrow() {
+        Some(sender) => sender.send(progress).is_ok(),
+        None => true,
+    })
+}
+
+/// Runs a solution on a separate thread and waits for it to finish.
+///
+/// Each step gets its own time limit, a part that exceeds it is reported as timed out and the
+/// remaining parts are skipped. There is no way to stop a thread, so a timed out solution keeps
+/// running in the background until it finishes or the process exits.
+/// [`DayResult::timed_out`] is set in that case.
+///
+/// With the `dhat-heap` feature, solutions are not run while a timed out part still holds the
+/// heap profiler, as only one may exist at a time.
+pub(crate) fn run_solution(solution: &Solution, input: String, options: &RunOptions) -> DayResult {
+    let id = solution.id;
+
+    #[cfg(feature = "dhat-heap")]
+    if IS_PROFILING.load(Ordering::SeqCst) {
+        let mut result = DayResult::new(id);
+        result.error =
+            Some("could not profile memory, a part that timed out is still running".into());
+        return result;
+    }
+
+    let timeout = solution
+        .timeout
+        .or(options.timeout)
+        .unwrap_or(config::get().run.timeout);
+
+    let (sender, receiver) = mpsc::channel();
+    let run = solution.run;
+    let thread_options = options.clone();
+    let capture = output::current();
+
+    let spawned = thread::Builder::new()
+        .name(id.to_string())
+        .stack_size(STACK_SIZE)
+        .spawn(move || {
+            output::set(capture);
+            PROGRESS.with(|progress| *progress.borrow_mut() = Some(sender.clone()));
+            // NOTE: panics in parts are caught by `run_part`, this catches panics while parsing.
+            let result = panic::catch_unwind(AssertUnwindSafe(|| run(&input, &thread_options)))
+                .map_err(|payload| format!("panicked: {}", panic_message(&*payload)));
+            let _ = sender.send(Progress::Done(result));
+        });
+
+    let mut result = DayResult::new(id);
+
+    if let Err(e) = spawned {
+        result.error = Some(format!("could not spawn a thread for the solution: {e}"));
+        return result;
+    }
+
+    let mut current_part = None;
+    let mut deadline = timeout;
+
+    loop {
+        match receiver.recv_timeout(deadline) {
+            Ok(Progress::Started(part)) => {
+                current_part = part;
+                deadline = timeout;
+            }
+            Ok(Progress::Ran) => {
+                // NOTE: benching is bounded by the bench budget, give it some leeway on top.
+                if options.timed {
+                    deadline = timeout + options.bench_budget();
+                }
+            }
+            Ok(Progress::Parse(stats)) => result.parse = Some(stats),
+            Ok(Progress::Part(part_result)) => result.parts.push(part_result),
+            Ok(Progress::Done(Ok(day_result))) => return day_result,
+            Ok(Progress::Done(Err(error))) => {
+                result.error = Some(error);
+                return result;
+            }
+            Err(RecvTimeoutError::Timeout) => {
+                result.timed_out = true;
+                match current_part {
+                    Some(part) => {
+                        let part_result = PartResult {
+                            part,
+                            answer: None,
+                            stats: BenchStats::from_samples(&[deadline]).unwrap(),
+                            memory: None,
+                            status: PartStatus::TimedOut,
+                        };
+
+                        match options.format {
+                            OutputFormat::Text => {
+                                outln!("Part {part}: {} ({deadline:.1?})", part_result.status);
+                            }
+                            OutputFormat::Ndjson => print_part_event(id, &part_result),
+                            OutputFormat::Json => {}
+                        }
+
+                        result.parts.push(part_result);
+                    }
+                    None => {
+                        result.error =
+                            Some(format!("timed out after {deadline:.1?} while parsing"));
+                    }
+                }
+                return result;
+            }
+            Err(RecvTimeoutError::Disconnected) => {
+                result.error = Some("the solution thread exited unexpectedly".into());
+                return result;
+            }
+        }
+    }
+}
+
+/// Creates the common fields of a NDJSON record.
+pub(crate) fn record(kind: &str, id: PuzzleId) -> HashMap<String, JsonValue> {
+    let mut map: HashMap<String, JsonValue> = HashMap::new();
+    map.insert("type".into(), JsonValue::String(kind.into()));
+    id.write_json(&mut map);
+    map
+}
+
+/// Run a solution part. The behavior differs depending on whether the run is timed:
+///  1. if not, the function is executed once.
+///  2. if it is, the function is benched. The number of samples is sized from the first run to
+///     take about a second, clamped to `bench.min_samples` (10 by default) and `bench.max_samples`
+///     (10000 by default). Benching stops early once the bench budget (`--bench-budget` or
+///     `bench.budget`, 5s by default) is spent, even below the minimum number of samples.
+///
+/// Benching is skipped if `is_benchable` returns false for the result of the first run.
 fn run_timed<I: Clone, T>(
     func: impl Fn(I) -> T,
     input: I,
+    options: &RunOptions,
     hook: impl Fn(&T),
-) -> (T, Duration, u128) {
+    is_benchable: impl Fn(&T) -> bool,
+) -> (T, BenchStats, Option<MemoryStats>) {
     let timer = Instant::now();
-    let result = {
+    let (result, base_time, memory) = {
         let input = input.clone();
 
-        #[cfg(feature = "dhat-heap")]
-        let _profiler = dhat::Profiler::new_heap();
-
-        func(input)
+        let profiler = HeapProfiler::start();
+        let result = func(input);
+        (result, timer.elapsed(), profiler.stats())
     };
-    let base_time = timer.elapsed();
 
     hook(&result);
 
-    let run = if std::env::args().any(|x| x == "--time") {
-        bench(func, input, &base_time)
+    let samples = if options.timed && is_benchable(&result) {
+        bench(
+            func,
+            input,
+            &base_time,
+            options.bench_budget(),
+            options.is_text(),
+        )
     } else {
-        (base_time, 1)
+        vec![base_time]
     };
 
-    (result, run.0, run.1)
+    // NOTE: `samples` always holds at least one value.
+    (result, BenchStats::from_samples(&samples).unwrap(), memory)
 }
 
-fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) -> (Duration, u128) {
-    let mut stdout = stdout();
+/// Whether a `dhat` profiler exists. A part that timed out keeps its profiler until it finishes.
+#[cfg(feature = "dhat-heap")]
+static IS_PROFILING: AtomicBool = AtomicBool::new(false);
 
-    print!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
-    let _ = stdout.flush();
+/// Profiles the heap usage of a single run, only available with the `dhat-heap` feature.
+#[cfg(feature = "dhat-heap")]
+struct HeapProfiler(Option<dhat::Profiler>);
 
-    let bench_iterations = cmp::min(
-        10000,
-        cmp::max(
-            Duration::from_secs(1).as_nanos() / cmp::max(base_time.as_nanos(), 10),
-            10,
-        ),
-    );
+#[cfg(feature = "dhat-heap")]
+impl HeapProfiler {
+    /// Starts profiling, unless another profiler still exists.
+    fn start() -> Self {
+        let is_free = IS_PROFILING
+            .compare_exchange(false, true, Ordering::SeqCst, Ordering::SeqCst)
+            .is_ok();
+        Self(is_free.then(dhat::Profiler::new_heap))
+    }
 
-    let mut timers: Vec<Duration> = vec![];
+    /// Heap usage since the profiler started.
+    fn stats(&self) -> Option<MemoryStats> {
+        self.0.as_ref()?;
+        let stats = dhat::HeapStats::get();
+        Some(MemoryStats {
+            total_bytes: stats.total_bytes,
+            total_blocks: stats.total_blocks,
+            max_bytes: stats.max_bytes as u64,
+        })
+    }
+}
+
+#[cfg(feature = "dhat-heap")]
+impl Drop for HeapProfiler {
+    fn drop(&mut self) {
+        if let Some(profiler) = self.0.take() {
+            drop(profiler);
+            IS_PROFILING.store(false, Ordering::SeqCst);
+        }
+    }
+}
+
+#[cfg(not(feature = "dhat-heap"))]
+struct HeapProfiler;
+
+#[cfg(not(feature = "dhat-heap"))]
+impl HeapProfiler {
+    fn start() -> Self {
+        Self
+    }
+
+    fn stats(&self) -> Option<MemoryStats> {
+        None
+    }
+}
+
+fn bench<I: Clone, T>(
+    func: impl Fn(I) -> T,
+    input: I,
+    base_time: &Duration,
+    budget: Duration,
+    show_progress: bool,
+) -> Vec<Duration> {
+    if show_progress {
+        out!(" > {ANSI_ITALIC}benching{ANSI_RESET}");
+    }
+
+    let config = &config::get().bench;
+    let bench_iterations = (Duration::from_secs(1).as_nanos() / base_time.as_nanos().max(10))
+        .clamp(config.min_samples, config.max_samples);
+
+    let mut timers: Vec<Duration> = Vec::with_capacity(bench_iterations as usize);
+
+    let started = Instant::now();
 
     for _ in 0..bench_iterations {
         // need a clone here to make the borrow checker happy.
@@ -74,21 +698,25 @@ fn bench<I: Clone, T>(func: impl Fn(I) -> T, input: I, base_time: &Duration) ->
         let timer = Instant::now();
         black_box(func(black_box(cloned)));
         timers.push(timer.elapsed());
+
+        // NOTE: slow parts may not reach the minimum number of samples within the budget.
+        if started.elapsed() > budget {
+            break;
+        }
     }
 
-    (
-        #[allow(clippy::cast_possible_truncation)]
-        Duration::from_nanos(average_duration(&timers) as u64),
-        bench_iterations,
-    )
+    timers
 }
 
-fn average_duration(numbers: &[Duration]) -> u128 {
-    numbers
-        .iter()
-        .map(std::time::Duration::as_nanos)
-        .sum::<u128>()
-        / numbers.len() as u128
+/// Extracts the message of a panic payload, which is either a `&str` or a `String`.
+pub(crate) fn panic_message(payload: &(dyn Any + Send)) -> String {
+    if let Some(message) = payload.downcast_ref::<&str>() {
+        (*message).to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "unknown panic payload".into()
+    }
 }
 
 fn format_duration(duration: &Duration, samples: u128) -> String {
@@ -99,6 +727,13 @@ fn format_duration(duration: &Duration, samples: u128) -> String {
     }
 }
 
+fn format_stats(stats: &BenchStats) -> String {
+    format!(
+        "min {:.1?} · median {:.1?} · p95 {:.1?} · max {:.1?} · stddev {:.1?} · {} outliers",
+        stats.min, stats.median, stats.p95, stats.max, stats.std_dev, stats.outliers
+    )
+}
+
 fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str) {
     let is_intermediate_result = duration_str.is_empty();
 
@@ -107,68 +742,331 @@ fn print_result<T: Display>(result: &Option<T>, part: &str, duration_str: &str)
             if result.to_string().contains('\n') {
                 let str = format!("{part}: ▼ {duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
-                    println!("{result}");
+                    out!("\r");
+                    outln!("{str}");
+                    outln!("{result}");
                 }
             } else {
                 let str = format!("{part}: {ANSI_BOLD}{result}{ANSI_RESET}{duration_str}");
                 if is_intermediate_result {
-                    print!("{str}");
+                    out!("{str}");
                 } else {
-                    print!("\r");
-                    println!("{str}");
+                    out!("\r");
+                    outln!("{str}");
                 }
             }
         }
         None => {
             if is_intermediate_result {
-                print!("{part}: ✖");
+                out!("{part}: ✖");
             } else {
-                print!("\r");
-                println!("{part}: ✖             ");
+                out!("\r");
+                outln!("{part}: ✖             ");
             }
         }
     }
 }
 
-/// Parse the arguments passed to `solve` and try to submit one part of the solution if:
-///  1. we are in `--release` mode.
-///  2. aoc-cli is installed.
-fn submit_result<T: Display>(
-    result: T,
-    day: Day,
-    part: u8,
-) -> Option<Result<Output, aoc_cli::AocCommandError>> {
-    let args: Vec<String> = env::args().collect();
+/// Store an accepted answer so that `cargo verify` can detect regressions.
+fn record_answer(answer: &str, id: PuzzleId, part: u8) {
+    // NOTE: never store answers that could not be read, that would drop every other answer.
+    let mut answers = match Answers::read_from_file() {
+        Ok(answers) => answers,
+        Err(e) => {
+            eprintln!("Failed to record answer: {e}");
+            if let Some(hint) = e.hint() {
+                eprintln!("Hint: {hint}");
+            }
+            return;
+        }
+    };
+    answers.set(id, part, answer);
+
+    match answers.store_file() {
+        Ok(()) => eprintln!("Recorded answer for {id}, part {part}."),
+        Err(e) => eprintln!("Failed to record answer: {e}"),
+    }
+}
+
+/// Submit one part of the solution to adventofcode.com.
+/// Answers that are known to be wrong or look invalid are refused unless forced.
+fn submit_result(result: &str, id: PuzzleId, part: u8, options: &RunOptions) {
+    // NOTE: never submit without the log, storing the new verdict would overwrite it.
+    let mut submissions = match Submissions::read_from_file() {
+        Ok(submissions) => submissions,
+        Err(e) => {
+            eprintln!("Refusing to submit \"{result}\": {e}");
+            if let Some(hint) = e.hint() {
+                eprintln!("Hint: {hint}");
+            }
+            return;
+        }
+    };
+
+    if let Err(rejection) = submissions.check(id, part, result) {
+        if options.force {
+            eprintln!("Submitting anyway: {rejection}.");
+        } else {
+            eprintln!(
+                "Refusing to submit \"{result}\": {rejection}. Pass --force to submit anyway."
+            );
+            return;
+        }
+    }
+
+    if options.is_text() {
+        println!("Submitting result...");
+    } else {
+        eprintln!("Submitting result...");
+    }
+
+    let message = match aoc_client::submit(id, part, result) {
+        Ok(message) => message,
+        Err(e) => {
+            eprintln!("failed to submit answer: {e}");
+            return;
+        }
+    };
+
+    if options.is_text() {
+        println!("{message}");
+    } else {
+        eprintln!("{message}");
+    }
+
+    if let Some(verdict) = Verdict::from_message(&message) {
+        submissions.record(id, part, result, verdict);
+
+        if let Err(e) = submissions.store_file() {
+            eprintln!("Failed to record submission: {e}");
+        }
+
+        if verdict == Verdict::Correct {
+            record_answer(result, id, part);
+        }
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&PartResult> for JsonValue {
+    fn from(value: &PartResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-    if !args.contains(&"--submit".into()) {
-        return None;
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert(
+            "answer".into(),
+            value
+                .answer
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
+        map.insert("stats".into(), JsonValue::from(&value.stats));
+        map.insert(
+            "memory".into(),
+            value
+                .memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "status".into(),
+            JsonValue::String(value.status.as_str().into()),
+        );
+
+        match &value.status {
+            PartStatus::Panicked(message) => {
+                map.insert("message".into(), JsonValue::String(message.clone()));
+            }
+            PartStatus::WrongAnswer { expected } => {
+                map.insert("expected".into(), JsonValue::String(expected.clone()));
+            }
+            _ => {}
+        }
+
+        JsonValue::Object(map)
     }
+}
+
+impl From<&DayResult> for JsonValue {
+    fn from(value: &DayResult) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        value.id.write_json(&mut map);
+        map.insert(
+            "parse".into(),
+            value
+                .parse
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+        map.insert(
+            "parts".into(),
+            JsonValue::Array(value.parts.iter().map(JsonValue::from).collect()),
+        );
+        map.insert(
+            "error".into(),
+            value
+                .error
+                .clone()
+                .map_or(JsonValue::Null, JsonValue::String),
+        );
 
-    if args.len() < 3 {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+        JsonValue::Object(map)
     }
+}
+
+/* -------------------------------------------------------------------------- */
 
-    let part_index = args.iter().position(|x| x == "--submit").unwrap() + 1;
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use std::{collections::HashMap, thread, time::Duration};
 
-    let Ok(part_submit) = args[part_index].parse::<u8>() else {
-        eprintln!("Unexpected command-line input. Format: cargo solve 1 --submit 1");
-        process::exit(1);
+    use tinyjson::JsonValue;
+
+    use super::{
+        parse_seconds, run_part, run_solution, DayResult, OutputFormat, PartResult, PartStatus,
+        RunOptions, Solution,
+    };
+    use crate::{
+        day,
+        template::{answers::Answers, stats::BenchStats, PuzzleId},
+        year,
     };
 
-    if part_submit != part {
-        return None;
+    #[test]
+    fn parses_output_formats() {
+        assert_eq!("text".parse(), Ok(OutputFormat::Text));
+        assert_eq!("json".parse(), Ok(OutputFormat::Json));
+        assert_eq!("ndjson".parse(), Ok(OutputFormat::Ndjson));
+        assert!("yaml".parse::<OutputFormat>().is_err());
     }
 
-    if aoc_cli::check().is_err() {
-        eprintln!("command \"aoc\" not found or not callable. Try running \"cargo install aoc-cli\" to install it.");
-        process::exit(1);
+    #[test]
+    fn serializes_day_results() {
+        let stats = BenchStats::from_samples(&[Duration::from_millis(1)]).unwrap();
+        let result = DayResult {
+            parts: vec![PartResult {
+                part: 1,
+                answer: Some("Part 1: (1ms @ 10 samples)".into()),
+                stats,
+                memory: None,
+                status: PartStatus::Solved,
+            }],
+            ..DayResult::new(PuzzleId::new(year!(2023), day!(5)))
+        };
+
+        let json = JsonValue::from(&result).stringify().unwrap();
+        let parsed: JsonValue = json.parse().unwrap();
+        let map = parsed.get::<HashMap<String, JsonValue>>().unwrap();
+
+        assert_eq!(map["year"], JsonValue::Number(2023.0));
+        assert_eq!(map["day"], JsonValue::String("05".into()));
+        assert!(map["parse"].is_null());
+        assert!(map["error"].is_null());
+
+        let parts = map["parts"].get::<Vec<JsonValue>>().unwrap();
+        let part = parts[0].get::<HashMap<String, JsonValue>>().unwrap();
+        assert_eq!(part["part"], JsonValue::Number(1.0));
+        assert_eq!(
+            part["answer"],
+            JsonValue::String("Part 1: (1ms @ 10 samples)".into())
+        );
+        assert_eq!(part["status"], JsonValue::String("solved".into()));
     }
 
-    println!("Submitting result via aoc-cli...");
-    Some(aoc_cli::submit(day, part, &result.to_string()))
+    #[test]
+    fn catches_panicking_parts() {
+        let result = run_part(
+            |_: &str| -> Option<u32> { panic!("oops") },
+            "",
+            PuzzleId::new(year!(2023), day!(5)),
+            1,
+            &RunOptions {
+                format: OutputFormat::Json,
+                ..RunOptions::default()
+            },
+        );
+
+        assert_eq!(result.answer, None);
+        assert_eq!(result.status, PartStatus::Panicked("oops".into()));
+    }
+
+    #[test]
+    fn parses_seconds() {
+        assert_eq!(parse_seconds("30"), Ok(Duration::from_secs(30)));
+        assert_eq!(parse_seconds("0.5"), Ok(Duration::from_millis(500)));
+        assert!(parse_seconds("-1").is_err());
+        assert!(parse_seconds("soon").is_err());
+    }
+
+    #[test]
+    fn times_out_slow_parts() {
+        let solution = Solution {
+            id: PuzzleId::new(year!(2023), day!(5)),
+            run: |input, options| {
+                let id = PuzzleId::new(year!(2023), day!(5));
+                let mut result = DayResult::new(id);
+                result
+                    .parts
+                    .push(run_part(|_| Some(1), input, id, 1, options));
+                result.parts.push(run_part(
+                    |_| {
+                        thread::sleep(Duration::from_millis(500));
+                        Some(2)
+                    },
+                    input,
+                    id,
+                    2,
+                    options,
+                ));
+                result
+            },
+            timeout: Some(Duration::from_millis(50)),
+        };
+
+        let options = RunOptions {
+            format: OutputFormat::Json,
+            ..RunOptions::default()
+        };
+        let result = run_solution(&solution, String::new(), &options);
+
+        assert_eq!(result.parts.len(), 2);
+        assert_eq!(result.parts[0].status, PartStatus::Solved);
+        assert_eq!(result.parts[1].status, PartStatus::TimedOut);
+        assert!(result.timed_out);
+    }
+
+    #[test]
+    fn marks_wrong_answers() {
+        let stats = BenchStats::from_samples(&[Duration::from_millis(1)]).unwrap();
+        let id = PuzzleId::new(year!(2023), day!(5));
+        let part = |part: u8, answer: &str| PartResult {
+            part,
+            answer: Some(answer.into()),
+            stats: stats.clone(),
+            memory: None,
+            status: PartStatus::Solved,
+        };
+
+        let mut answers = Answers::default();
+        answers.set(id, 1, "42");
+        answers.set(id, 2, "7");
+
+        let mut result = DayResult {
+            parts: vec![part(1, "42"), part(2, "8")],
+            ..DayResult::new(id)
+        };
+        result.check_answers(&answers);
+
+        assert_eq!(result.parts[0].status, PartStatus::Solved);
+        assert_eq!(
+            result.parts[1].status,
+            PartStatus::WrongAnswer {
+                expected: "7".into()
+            }
+        );
+    }
 }
@@ -0,0 +1,177 @@
+/// Summary statistics for the samples collected while benching a solution part.
+use std::{fmt::Display, time::Duration};
+
+/// Distribution of execution times over a set of benchmark samples.
+#[derive(Clone, Debug, PartialEq)]
+pub struct BenchStats {
+    pub samples: u128,
+    pub mean: Duration,
+    pub min: Duration,
+    pub median: Duration,
+    pub p95: Duration,
+    pub max: Duration,
+    pub std_dev: Duration,
+    /// Number of samples outside of the Tukey fences (1.5 × IQR beyond the quartiles).
+    pub outliers: usize,
+}
+
+impl BenchStats {
+    /// Computes statistics over the provided samples. Returns [`None`] if there are no samples.
+    pub fn from_samples(samples: &[Duration]) -> Option<Self> {
+        if samples.is_empty() {
+            return None;
+        }
+
+        let mut sorted = samples.to_vec();
+        sorted.sort_unstable();
+
+        let nanos: Vec<f64> = sorted.iter().map(|d| d.as_nanos() as f64).collect();
+        let count = nanos.len() as f64;
+        let mean = nanos.iter().sum::<f64>() / count;
+        let variance = nanos.iter().map(|x| (x - mean).powi(2)).sum::<f64>() / count;
+
+        let q1 = percentile(&nanos, 25.0);
+        let q3 = percentile(&nanos, 75.0);
+        let iqr = q3 - q1;
+        let (lower_fence, upper_fence) = (q1 - 1.5 * iqr, q3 + 1.5 * iqr);
+        let outliers = nanos
+            .iter()
+            .filter(|&&x| x < lower_fence || x > upper_fence)
+            .count();
+
+        Some(Self {
+            samples: sorted.len() as u128,
+            mean: from_nanos(mean),
+            min: sorted[0],
+            median: from_nanos(percentile(&nanos, 50.0)),
+            p95: from_nanos(percentile(&nanos, 95.0)),
+            max: sorted[sorted.len() - 1],
+            std_dev: from_nanos(variance.sqrt()),
+            outliers,
+        })
+    }
+}
+
+/// Heap usage of a single run of a solution part, measured with `dhat`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MemoryStats {
+    /// Bytes allocated over the entire run.
+    pub total_bytes: u64,
+    /// Number of allocations over the entire run.
+    pub total_blocks: u64,
+    /// Bytes allocated at the peak of heap usage.
+    pub max_bytes: u64,
+}
+
+impl Display for MemoryStats {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} in {} allocations · peak {}",
+            format_bytes(self.total_bytes),
+            self.total_blocks,
+            format_bytes(self.max_bytes)
+        )
+    }
+}
+
+/// Formats a number of bytes with a binary unit, e.g. `1.5 KiB`.
+#[allow(clippy::cast_precision_loss)]
+pub fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+
+    let mut value = bytes as f64 / 1024.0;
+    let mut unit = 0;
+
+    while value >= 1024.0 && unit < UNITS.len() - 1 {
+        value /= 1024.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+/// Linearly interpolated percentile of an already sorted, non-empty slice.
+fn percentile(sorted: &[f64], p: f64) -> f64 {
+    let rank = p / 100.0 * (sorted.len() - 1) as f64;
+    let lower = rank.floor() as usize;
+    let upper = rank.ceil() as usize;
+    sorted[lower] + (sorted[upper] - sorted[lower]) * (rank - lower as f64)
+}
+
+#[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+fn from_nanos(nanos: f64) -> Duration {
+    Duration::from_nanos(nanos.round() as u64)
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{format_bytes, BenchStats, MemoryStats};
+    use std::time::Duration;
+
+    fn ms(values: &[u64]) -> Vec<Duration> {
+        values.iter().map(|x| Duration::from_millis(*x)).collect()
+    }
+
+    #[test]
+    fn handles_empty_samples() {
+        assert_eq!(BenchStats::from_samples(&[]), None);
+    }
+
+    #[test]
+    fn handles_single_sample() {
+        let stats = BenchStats::from_samples(&ms(&[5])).unwrap();
+        assert_eq!(stats.samples, 1);
+        assert_eq!(stats.min, Duration::from_millis(5));
+        assert_eq!(stats.median, Duration::from_millis(5));
+        assert_eq!(stats.p95, Duration::from_millis(5));
+        assert_eq!(stats.max, Duration::from_millis(5));
+        assert_eq!(stats.std_dev, Duration::ZERO);
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn computes_distribution() {
+        let stats = BenchStats::from_samples(&ms(&[4, 1, 3, 2, 5])).unwrap();
+        assert_eq!(stats.samples, 5);
+        assert_eq!(stats.mean, Duration::from_millis(3));
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.median, Duration::from_millis(3));
+        assert_eq!(stats.p95, Duration::from_micros(4800));
+        assert_eq!(stats.max, Duration::from_millis(5));
+        assert_eq!(stats.std_dev, Duration::from_nanos(1_414_214));
+        assert_eq!(stats.outliers, 0);
+    }
+
+    #[test]
+    fn detects_outliers() {
+        let stats = BenchStats::from_samples(&ms(&[10, 10, 11, 10, 12, 10, 11, 250])).unwrap();
+        assert_eq!(stats.outliers, 1);
+        assert_eq!(stats.median, Duration::from_micros(10500));
+        assert_eq!(stats.max, Duration::from_millis(250));
+    }
+
+    #[test]
+    fn formats_bytes() {
+        assert_eq!(format_bytes(0), "0 B");
+        assert_eq!(format_bytes(1023), "1023 B");
+        assert_eq!(format_bytes(1536), "1.5 KiB");
+        assert_eq!(format_bytes(5 * 1024 * 1024), "5.0 MiB");
+    }
+
+    #[test]
+    fn formats_memory_stats() {
+        let stats = MemoryStats {
+            total_bytes: 2048,
+            total_blocks: 12,
+            max_bytes: 512,
+        };
+        assert_eq!(stats.to_string(), "2.0 KiB in 12 allocations · peak 512 B");
+    }
+}
@@ -0,0 +1,441 @@
+/// Log of submitted answers, used to refuse submissions that are known to be wrong.
+use std::{
+    collections::HashMap,
+    fmt::Display,
+    fs,
+    io::ErrorKind,
+    str::FromStr,
+    time::{SystemTime, UNIX_EPOCH},
+};
+use tinyjson::JsonValue;
+
+use crate::template::{config, error::TemplateError, PuzzleId};
+
+static SUBMISSIONS_FILE_NAME: &str = "submissions.json";
+
+/// The feedback adventofcode.com gave on a submitted answer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Verdict {
+    Correct,
+    TooHigh,
+    TooLow,
+    Wrong,
+}
+
+impl Verdict {
+    /// Reads the verdict from the response to a submission. Returns `None` for responses that
+    /// don't judge the answer, e.g. when submitting too quickly or for an already solved part.
+    pub fn from_message(message: &str) -> Option<Self> {
+        if message.contains("That's the right answer") {
+            Some(Self::Correct)
+        } else if message.contains("That's not the right answer") {
+            if message.contains("your answer is too high") {
+                Some(Self::TooHigh)
+            } else if message.contains("your answer is too low") {
+                Some(Self::TooLow)
+            } else {
+                Some(Self::Wrong)
+            }
+        } else {
+            None
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Correct => "correct",
+            Self::TooHigh => "too_high",
+            Self::TooLow => "too_low",
+            Self::Wrong => "wrong",
+        }
+    }
+}
+
+impl FromStr for Verdict {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "correct" => Ok(Self::Correct),
+            "too_high" => Ok(Self::TooHigh),
+            "too_low" => Ok(Self::TooLow),
+            "wrong" => Ok(Self::Wrong),
+            x => Err(format!("unknown verdict `{x}`")),
+        }
+    }
+}
+
+/// A single submitted answer.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Submission {
+    pub id: PuzzleId,
+    pub part: u8,
+    pub answer: String,
+    pub verdict: Verdict,
+    /// Seconds since the unix epoch.
+    pub timestamp: u64,
+}
+
+/// Why an answer was not submitted.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Rejection {
+    Empty,
+    Multiline,
+    Zero,
+    Negative,
+    AlreadySolved(String),
+    KnownWrong(Verdict),
+    TooHigh(String),
+    TooLow(String),
+}
+
+impl Display for Rejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Rejection::Empty => write!(f, "the answer is empty"),
+            Rejection::Multiline => write!(f, "the answer spans multiple lines"),
+            Rejection::Zero => write!(f, "the answer is zero"),
+            Rejection::Negative => write!(f, "the answer is negative"),
+            Rejection::AlreadySolved(answer) => {
+                write!(f, "this part was already solved with `{answer}`")
+            }
+            Rejection::KnownWrong(Verdict::TooHigh) => {
+                write!(f, "this answer was already submitted and was too high")
+            }
+            Rejection::KnownWrong(Verdict::TooLow) => {
+                write!(f, "this answer was already submitted and was too low")
+            }
+            Rejection::KnownWrong(_) => {
+                write!(f, "this answer was already submitted and was wrong")
+            }
+            Rejection::TooHigh(bound) => {
+                write!(
+                    f,
+                    "the answer is not lower than `{bound}`, which was too high"
+                )
+            }
+            Rejection::TooLow(bound) => {
+                write!(
+                    f,
+                    "the answer is not higher than `{bound}`, which was too low"
+                )
+            }
+        }
+    }
+}
+
+/// Represents all submissions made so far.
+/// Can be serialized from / to JSON.
+#[derive(Clone, Debug, Default)]
+pub struct Submissions {
+    pub data: Vec<Submission>,
+}
+
+impl Submissions {
+    /// Dehydrate submissions to a JSON file.
+    pub fn store_file(&self) -> Result<(), TemplateError> {
+        let json = JsonValue::from(self.clone());
+        let path = config::get().paths.data_file(SUBMISSIONS_FILE_NAME);
+        fs::File::create(&path)
+            .and_then(|mut file| json.format_to(&mut file))
+            .map_err(|source| TemplateError::Io { path, source })
+    }
+
+    /// Rehydrate submissions from a JSON file. If not present, returns an empty log.
+    pub fn read_from_file() -> Result<Self, TemplateError> {
+        let path = config::get().paths.data_file(SUBMISSIONS_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(s) => Submissions::try_from(s)
+                .map_err(|reason| TemplateError::MalformedData { path, reason }),
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Submissions::default()),
+            Err(source) => Err(TemplateError::Io { path, source }),
+        }
+    }
+
+    /// Adds a judged submission to the log.
+    pub fn record(&mut self, id: PuzzleId, part: u8, answer: &str, verdict: Verdict) {
+        self.data.push(Submission {
+            id,
+            part,
+            // NOTE: trimmed like in `check`, so that the answer is found there again.
+            answer: answer.trim().into(),
+            verdict,
+            timestamp: SystemTime::now()
+                .duration_since(UNIX_EPOCH)
+                .map(|d| d.as_secs())
+                .unwrap_or_default(),
+        });
+    }
+
+    /// Checks an answer against the log before submitting it.
+    pub fn check(&self, id: PuzzleId, part: u8, answer: &str) -> Result<(), Rejection> {
+        let answer = answer.trim();
+
+        if answer.is_empty() {
+            return Err(Rejection::Empty);
+        }
+
+        if answer.contains('\n') {
+            return Err(Rejection::Multiline);
+        }
+
+        let value = answer.parse::<i128>().ok();
+
+        match value {
+            Some(0) => return Err(Rejection::Zero),
+            Some(x) if x < 0 => return Err(Rejection::Negative),
+            _ => {}
+        }
+
+        let submissions: Vec<&Submission> = self
+            .data
+            .iter()
+            .filter(|s| s.id == id && s.part == part)
+            .collect();
+
+        if let Some(correct) = submissions.iter().find(|s| s.verdict == Verdict::Correct) {
+            return Err(Rejection::AlreadySolved(correct.answer.clone()));
+        }
+
+        if let Some(previous) = submissions.iter().find(|s| s.answer == answer) {
+            return Err(Rejection::KnownWrong(previous.verdict));
+        }
+
+        let Some(value) = value else {
+            return Ok(());
+        };
+
+        let bound = |verdict: Verdict| {
+            submissions
+                .iter()
+                .filter(move |s| s.verdict == verdict)
+                .filter_map(|s| Some((s.answer.parse::<i128>().ok()?, &s.answer)))
+        };
+
+        if let Some((_, answer)) = bound(Verdict::TooHigh)
+            .filter(|(bound, _)| value >= *bound)
+            .min_by_key(|(bound, _)| *bound)
+        {
+            return Err(Rejection::TooHigh(answer.clone()));
+        }
+
+        if let Some((_, answer)) = bound(Verdict::TooLow)
+            .filter(|(bound, _)| value <= *bound)
+            .max_by_key(|(bound, _)| *bound)
+        {
+            return Err(Rejection::TooLow(answer.clone()));
+        }
+
+        Ok(())
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<Submissions> for JsonValue {
+    fn from(value: Submissions) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "data".into(),
+            JsonValue::Array(value.data.iter().map(JsonValue::from).collect()),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<String> for Submissions {
+    type Error = String;
+
+    fn try_from(value: String) -> Result<Self, Self::Error> {
+        let json = JsonValue::from_str(&value).or(Err("not valid JSON file."))?;
+
+        let json_data = json
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("expected JSON document to be an object.")?
+            .get("data")
+            .ok_or("expected JSON document to have key `data`.")?
+            .get::<Vec<JsonValue>>()
+            .ok_or("expected `json.data` to be an array.")?;
+
+        Ok(Submissions {
+            data: json_data
+                .iter()
+                .map(Submission::try_from)
+                .collect::<Result<_, _>>()?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&Submission> for JsonValue {
+    fn from(value: &Submission) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        value.id.write_json(&mut map);
+        map.insert("part".into(), JsonValue::Number(f64::from(value.part)));
+        map.insert("answer".into(), JsonValue::String(value.answer.clone()));
+        map.insert(
+            "verdict".into(),
+            JsonValue::String(value.verdict.as_str().into()),
+        );
+        map.insert(
+            "timestamp".into(),
+            JsonValue::Number(value.timestamp as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for Submission {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected submission to be a JSON object.")?;
+
+        let id = PuzzleId::read_json(json, "submission")?;
+
+        let part = json
+            .get("part")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.part to be a number.")?;
+
+        let answer = json
+            .get("answer")
+            .and_then(|v| v.get::<String>())
+            .ok_or("Expected submission.answer to be a string.")?;
+
+        let verdict = json
+            .get("verdict")
+            .and_then(|v| v.get::<String>())
+            .and_then(|v| Verdict::from_str(v).ok())
+            .ok_or("Expected submission.verdict to be a verdict.")?;
+
+        let timestamp = json
+            .get("timestamp")
+            .and_then(|v| v.get::<f64>())
+            .ok_or("Expected submission.timestamp to be a number.")?;
+
+        Ok(Submission {
+            id,
+            part: *part as u8,
+            answer: answer.clone(),
+            verdict,
+            timestamp: *timestamp as u64,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{Rejection, Submissions, Verdict};
+    use crate::puzzle;
+
+    #[test]
+    fn reads_verdicts() {
+        assert_eq!(
+            Verdict::from_message("That's the right answer! You are one gold star closer."),
+            Some(Verdict::Correct)
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer; your answer is too high."),
+            Some(Verdict::TooHigh)
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer; your answer is too low."),
+            Some(Verdict::TooLow)
+        );
+        assert_eq!(
+            Verdict::from_message("That's not the right answer. If you're stuck, ..."),
+            Some(Verdict::Wrong)
+        );
+        assert_eq!(
+            Verdict::from_message("You gave an answer too recently; you have 30s left to wait."),
+            None
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_answers() {
+        let submissions = Submissions::default();
+        assert_eq!(
+            submissions.check(puzzle!(2023, 1), 1, "0"),
+            Err(Rejection::Zero)
+        );
+        assert_eq!(
+            submissions.check(puzzle!(2023, 1), 1, "-12"),
+            Err(Rejection::Negative)
+        );
+        assert_eq!(
+            submissions.check(puzzle!(2023, 1), 1, "a\nb"),
+            Err(Rejection::Multiline)
+        );
+        assert_eq!(
+            submissions.check(puzzle!(2023, 1), 1, " "),
+            Err(Rejection::Empty)
+        );
+        assert_eq!(submissions.check(puzzle!(2023, 1), 1, "142"), Ok(()));
+        assert_eq!(submissions.check(puzzle!(2023, 1), 1, "ABCDEF"), Ok(()));
+    }
+
+    #[test]
+    fn rejects_answers_outside_bounds() {
+        let mut submissions = Submissions::default();
+        submissions.record(puzzle!(2023, 5), 1, "500", Verdict::TooHigh);
+        submissions.record(puzzle!(2023, 5), 1, "800", Verdict::TooHigh);
+        submissions.record(puzzle!(2023, 5), 1, "100", Verdict::TooLow);
+        submissions.record(puzzle!(2023, 5), 1, "300", Verdict::Wrong);
+
+        assert_eq!(
+            submissions.check(puzzle!(2023, 5), 1, "300"),
+            Err(Rejection::KnownWrong(Verdict::Wrong))
+        );
+        assert_eq!(
+            submissions.check(puzzle!(2023, 5), 1, "900"),
+            Err(Rejection::TooHigh("500".into()))
+        );
+        assert_eq!(
+            submissions.check(puzzle!(2023, 5), 1, "50"),
+            Err(Rejection::TooLow("100".into()))
+        );
+        assert_eq!(submissions.check(puzzle!(2023, 5), 1, "250"), Ok(()));
+        assert_eq!(submissions.check(puzzle!(2023, 5), 2, "900"), Ok(()));
+
+        submissions.record(puzzle!(2023, 5), 1, "250", Verdict::Correct);
+        assert_eq!(
+            submissions.check(puzzle!(2023, 5), 1, "251"),
+            Err(Rejection::AlreadySolved("250".into()))
+        );
+    }
+
+    #[test]
+    fn trims_recorded_answers() {
+        let mut submissions = Submissions::default();
+        submissions.record(puzzle!(2023, 3), 1, " 4361\n", Verdict::Wrong);
+
+        assert_eq!(
+            submissions.check(puzzle!(2023, 3), 1, "4361"),
+            Err(Rejection::KnownWrong(Verdict::Wrong))
+        );
+    }
+
+    #[test]
+    fn roundtrips_submissions() {
+        let mut submissions = Submissions::default();
+        submissions.record(puzzle!(2023, 7), 2, "5905", Verdict::TooLow);
+
+        let json = tinyjson::JsonValue::from(submissions.clone())
+            .stringify()
+            .unwrap();
+        let parsed = Submissions::try_from(json).unwrap();
+        assert_eq!(parsed.data, submissions.data);
+    }
+}
@@ -1,16 +1,33 @@
-use std::{collections::HashMap, fs, io::Error, str::FromStr};
+use std::{collections::HashMap, fs, io::ErrorKind, str::FromStr, time::Duration};
 use tinyjson::JsonValue;
 
-use crate::template::Day;
+use crate::template::{
+    config,
+    error::TemplateError,
+    runner::{DayResult, PartResult},
+    stats::{BenchStats, MemoryStats},
+    PuzzleId,
+};
 
-static TIMINGS_FILE_PATH: &str = "./data/timings.json";
+static TIMINGS_FILE_NAME: &str = "timings.json";
 
 /// Represents benchmark times for a single day.
 #[derive(Clone, Debug)]
 pub struct Timing {
-    pub day: Day,
+    pub id: PuzzleId,
+    pub parse: Option<String>,
     pub part_1: Option<String>,
     pub part_2: Option<String>,
+    pub parse_stats: Option<BenchStats>,
+    pub part_1_stats: Option<BenchStats>,
+    pub part_2_stats: Option<BenchStats>,
+    /// Heap usage of part 1, recorded with `cargo time --memory`.
+    pub part_1_memory: Option<MemoryStats>,
+    /// Heap usage of part 2, recorded with `cargo time --memory`.
+    pub part_2_memory: Option<MemoryStats>,
+    /// Parts that did not solve in the latest run and kept the timing of an earlier run.
+    /// They are not counted in `total_nanos` and do not make the day complete.
+    pub stale_parts: Vec<u8>,
     pub total_nanos: f64,
 }
 
@@ -23,42 +40,80 @@ pub struct Timings {
 
 impl Timings {
     /// Dehydrate timings to a JSON file.
-    pub fn store_file(&self) -> Result<(), Error> {
+    pub fn store_file(&self) -> Result<(), TemplateError> {
         let json = JsonValue::from(self.clone());
-        let mut file = fs::File::create(TIMINGS_FILE_PATH)?;
-        json.format_to(&mut file)
+        let path = config::get().paths.data_file(TIMINGS_FILE_NAME);
+        fs::File::create(&path)
+            .and_then(|mut file| json.format_to(&mut file))
+            .map_err(|source| TemplateError::Io { path, source })
     }
 
     /// Rehydrate timings from a JSON file. If not present, returns empty timings.
-    pub fn read_from_file() -> Self {
-        let s = fs::read_to_string(TIMINGS_FILE_PATH)
-            .map_err(|x| x.to_string())
-            .and_then(Timings::try_from);
-
-        match s {
-            Ok(timings) => timings,
-            Err(e) => {
-                eprintln!("{e}");
-                Timings::default()
+    pub fn read_from_file() -> Result<Self, TemplateError> {
+        let path = config::get().paths.data_file(TIMINGS_FILE_NAME);
+        match fs::read_to_string(&path) {
+            Ok(s) => {
+                Timings::try_from(s).map_err(|reason| TemplateError::MalformedData { path, reason })
             }
+            Err(e) if e.kind() == ErrorKind::NotFound => Ok(Timings::default()),
+            Err(source) => Err(TemplateError::Io { path, source }),
         }
     }
 
     /// Merge two sets of timings, overwriting `self` with `other` if present.
+    /// Parts that did not solve in `other` keep their stored timing as stale, and memory usage is
+    /// kept unless `other` has newer numbers, as it is recorded separately.
     pub fn merge(&self, new: &Self) -> Self {
         let mut data: Vec<Timing> = vec![];
 
         for timing in &new.data {
-            data.push(timing.clone());
+            let mut timing = timing.clone();
+            if let Some(stored) = self.data.iter().find(|t| t.id == timing.id) {
+                timing.keep_stored_parts(stored);
+                timing.part_1_memory = timing.part_1_memory.or(stored.part_1_memory.clone());
+                timing.part_2_memory = timing.part_2_memory.or(stored.part_2_memory.clone());
+            }
+            data.push(timing);
         }
 
         for timing in &self.data {
-            if !data.iter().any(|t| t.day == timing.day) {
+            if !data.iter().any(|t| t.id == timing.id) {
                 data.push(timing.clone());
             }
         }
 
-        data.sort_unstable_by(|a, b| a.day.cmp(&b.day));
+        data.sort_unstable_by_key(|t| t.id);
+        Timings { data }
+    }
+
+    /// Merge the memory usage of `other` into `self`, keeping the durations of `self`.
+    /// Used by `cargo time --memory`, whose durations are skewed by the heap profiler.
+    pub fn merge_memory(&self, new: &Self) -> Self {
+        let mut data = self.data.clone();
+
+        for timing in &new.data {
+            match data.iter_mut().find(|t| t.id == timing.id) {
+                Some(stored) => {
+                    stored.part_1_memory = timing.part_1_memory.clone();
+                    stored.part_2_memory = timing.part_2_memory.clone();
+                }
+                None => data.push(Timing {
+                    id: timing.id,
+                    parse: None,
+                    part_1: None,
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: timing.part_1_memory.clone(),
+                    part_2_memory: timing.part_2_memory.clone(),
+                    stale_parts: vec![],
+                    total_nanos: 0_f64,
+                }),
+            }
+        }
+
+        data.sort_unstable_by_key(|t| t.id);
         Timings { data }
     }
 
@@ -67,10 +122,137 @@ impl Timings {
         self.data.iter().map(|x| x.total_nanos).sum::<f64>() / 1_000_000_f64
     }
 
-    pub fn is_day_complete(&self, day: Day) -> bool {
-        self.data
+    pub fn is_day_complete(&self, id: PuzzleId) -> bool {
+        self.data.iter().any(|t| {
+            t.id == id && t.part_1.is_some() && t.part_2.is_some() && t.stale_parts.is_empty()
+        })
+    }
+}
+
+impl Timing {
+    /// Takes the stored timing of parts that did not solve and flags them as stale.
+    /// The total is left as is, as it only counts the parts that solved in this run.
+    fn keep_stored_parts(&mut self, stored: &Timing) {
+        type Part = fn(&mut Timing) -> (&mut Option<String>, &mut Option<BenchStats>);
+        let parts: [(u8, Part); 2] = [
+            (1, |t| (&mut t.part_1, &mut t.part_1_stats)),
+            (2, |t| (&mut t.part_2, &mut t.part_2_stats)),
+        ];
+
+        let mut stored = stored.clone();
+
+        for (part, get) in parts {
+            let (formatted, stats) = get(self);
+            if formatted.is_none() && stats.is_none() {
+                let (stored_formatted, stored_stats) = get(&mut stored);
+                if stored_formatted.is_some() {
+                    *formatted = stored_formatted.take();
+                    *stats = stored_stats.take();
+                    self.stale_parts.push(part);
+                }
+            }
+        }
+    }
+
+    /// Mean duration of the parse step in nanoseconds, if timed.
+    pub fn parse_nanos(&self) -> Option<f64> {
+        step_nanos(self.parse.as_deref(), self.parse_stats.as_ref())
+    }
+
+    /// Mean duration of part 1 in nanoseconds, if timed.
+    pub fn part_1_nanos(&self) -> Option<f64> {
+        step_nanos(self.part_1.as_deref(), self.part_1_stats.as_ref())
+    }
+
+    /// Mean duration of part 2 in nanoseconds, if timed.
+    pub fn part_2_nanos(&self) -> Option<f64> {
+        step_nanos(self.part_2.as_deref(), self.part_2_stats.as_ref())
+    }
+}
+
+/// Prefers the exact mean of the statistics, timings stored by older versions only have the formatted duration.
+fn step_nanos(formatted: Option<&str>, stats: Option<&BenchStats>) -> Option<f64> {
+    match stats {
+        Some(stats) => Some(stats.mean.as_nanos() as f64),
+        None => parse_duration(formatted?),
+    }
+}
+
+/// Parse a duration formatted with `{:.1?}` to nanoseconds.
+fn parse_duration(s: &str) -> Option<f64> {
+    // for possible time formats, see: https://github.com/rust-lang/rust/blob/1.64.0/library/core/src/time.rs#L1176-L1200
+    let parse = |postfix: &str| s.strip_suffix(postfix)?.parse::<f64>().ok();
+
+    if s.ends_with("ns") {
+        parse("ns")
+    } else if s.ends_with("µs") {
+        parse("µs").map(|x| x * 1000_f64)
+    } else if s.ends_with("ms") {
+        parse("ms").map(|x| x * 1_000_000_f64)
+    } else {
+        parse("s").map(|x| x * 1_000_000_000_f64)
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+impl From<&DayResult> for Timing {
+    fn from(value: &DayResult) -> Self {
+        let part_stats = |part: u8| {
+            value
+                .parts
+                .iter()
+                // NOTE: parts that were not solved would skew the totals.
+                .find(|p| p.part == part && p.status.is_solved())
+                .map(|p: &PartResult| p.stats.clone())
+        };
+
+        let part_memory = |part: u8| {
+            value
+                .parts
+                .iter()
+                .find(|p| p.part == part && p.status.is_solved())
+                .and_then(|p| p.memory.clone())
+        };
+
+        let parse_stats = value.parse.clone();
+        let part_1_stats = part_stats(1);
+        let part_2_stats = part_stats(2);
+
+        let format = |stats: &Option<BenchStats>| stats.as_ref().map(|s| format!("{:.1?}", s.mean));
+
+        let total_nanos = [&parse_stats, &part_1_stats, &part_2_stats]
             .iter()
-            .any(|t| t.day == day && t.part_1.is_some() && t.part_2.is_some())
+            .filter_map(|stats| stats.as_ref())
+            .map(|stats| stats.mean.as_nanos() as f64)
+            .sum();
+
+        Timing {
+            id: value.id,
+            parse: format(&parse_stats),
+            part_1: format(&part_1_stats),
+            part_2: format(&part_2_stats),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            part_1_memory: part_memory(1),
+            part_2_memory: part_memory(2),
+            stale_parts: vec![],
+            total_nanos,
+        }
+    }
+}
+
+impl From<&[DayResult]> for Timings {
+    /// Collects the timings of all days that could be run.
+    fn from(value: &[DayResult]) -> Self {
+        Timings {
+            data: value
+                .iter()
+                .filter(|result| result.error.is_none())
+                .map(Timing::from)
+                .collect(),
+        }
     }
 }
 
@@ -118,12 +300,21 @@ impl From<&Timing> for JsonValue {
     fn from(value: &Timing) -> Self {
         let mut map: HashMap<String, JsonValue> = HashMap::new();
 
-        map.insert("day".into(), JsonValue::String(value.day.to_string()));
+        value.id.write_json(&mut map);
         map.insert("total_nanos".into(), JsonValue::Number(value.total_nanos));
 
+        let parse = value.parse.clone().map(JsonValue::String);
         let part_1 = value.part_1.clone().map(JsonValue::String);
         let part_2 = value.part_2.clone().map(JsonValue::String);
 
+        map.insert(
+            "parse".into(),
+            match parse {
+                Some(x) => x,
+                None => JsonValue::Null,
+            },
+        );
+
         map.insert(
             "part_1".into(),
             match part_1 {
@@ -140,6 +331,57 @@ impl From<&Timing> for JsonValue {
             },
         );
 
+        map.insert(
+            "parse_stats".into(),
+            value
+                .parse_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_stats".into(),
+            value
+                .part_1_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_stats".into(),
+            value
+                .part_2_stats
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_1_memory".into(),
+            value
+                .part_1_memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "part_2_memory".into(),
+            value
+                .part_2_memory
+                .as_ref()
+                .map_or(JsonValue::Null, JsonValue::from),
+        );
+
+        map.insert(
+            "stale_parts".into(),
+            JsonValue::Array(
+                value
+                    .stale_parts
+                    .iter()
+                    .map(|&part| JsonValue::Number(f64::from(part)))
+                    .collect(),
+            ),
+        );
+
         JsonValue::Object(map)
     }
 }
@@ -152,11 +394,12 @@ impl TryFrom<&JsonValue> for Timing {
             .get::<HashMap<String, JsonValue>>()
             .ok_or("Expected timing to be a JSON object.")?;
 
-        let day = json
-            .get("day")
-            .and_then(|v| v.get::<String>())
-            .and_then(|day| Day::from_str(day).ok())
-            .ok_or("Expected timing.day to be a Day struct.")?;
+        let id = PuzzleId::read_json(json, "timing")?;
+
+        // solutions without a separate parse step do not store a parse timing.
+        let parse = json
+            .get("parse")
+            .and_then(|v| if v.is_null() { None } else { v.get::<String>() });
 
         let part_1 = json
             .get("part_1")
@@ -173,10 +416,58 @@ impl TryFrom<&JsonValue> for Timing {
             .and_then(|v| v.get::<f64>().copied())
             .ok_or("Expected timing.total_nanos to be a number.")?;
 
+        // statistics are not present in timings stored by older versions of the template.
+        let parse_stats = match json.get("parse_stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_1_stats = match json.get("part_1_stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_stats = match json.get("part_2_stats") {
+            Some(v) if !v.is_null() => Some(BenchStats::try_from(v)?),
+            _ => None,
+        };
+
+        // memory is only present for days recorded with `cargo time --memory`.
+        let part_1_memory = match json.get("part_1_memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        let part_2_memory = match json.get("part_2_memory") {
+            Some(v) if !v.is_null() => Some(MemoryStats::try_from(v)?),
+            _ => None,
+        };
+
+        // only present for days with parts that did not solve in the latest run.
+        let stale_parts = match json.get("stale_parts") {
+            Some(v) if !v.is_null() => v
+                .get::<Vec<JsonValue>>()
+                .ok_or("Expected timing.stale_parts to be an array.")?
+                .iter()
+                .map(|part| match part.get::<f64>() {
+                    Some(&part) if part == 1.0 || part == 2.0 => Ok(part as u8),
+                    _ => Err("Expected timing.stale_parts to hold part numbers."),
+                })
+                .collect::<Result<_, _>>()?,
+            _ => vec![],
+        };
+
         Ok(Timing {
-            day,
+            id,
+            parse: parse.cloned(),
             part_1: part_1.cloned(),
             part_2: part_2.cloned(),
+            parse_stats,
+            part_1_stats,
+            part_2_stats,
+            part_1_memory,
+            part_2_memory,
+            stale_parts,
             total_nanos,
         })
     }
@@ -184,9 +475,106 @@ impl TryFrom<&JsonValue> for Timing {
 
 /* -------------------------------------------------------------------------- */
 
-#[cfg(feature = "test_lib")]
+impl From<&BenchStats> for JsonValue {
+    fn from(value: &BenchStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        let nanos = |d: Duration| JsonValue::Number(d.as_nanos() as f64);
+
+        map.insert("samples".into(), JsonValue::Number(value.samples as f64));
+        map.insert("mean_nanos".into(), nanos(value.mean));
+        map.insert("min_nanos".into(), nanos(value.min));
+        map.insert("median_nanos".into(), nanos(value.median));
+        map.insert("p95_nanos".into(), nanos(value.p95));
+        map.insert("max_nanos".into(), nanos(value.max));
+        map.insert("std_dev_nanos".into(), nanos(value.std_dev));
+        map.insert("outliers".into(), JsonValue::Number(value.outliers as f64));
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for BenchStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing stats to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .ok_or(format!("Expected timing stats.{key} to be a number."))
+        };
+
+        let duration = |key: &str| number(key).map(|x| Duration::from_nanos(x as u64));
+
+        Ok(BenchStats {
+            samples: number("samples")? as u128,
+            mean: duration("mean_nanos")?,
+            min: duration("min_nanos")?,
+            median: duration("median_nanos")?,
+            p95: duration("p95_nanos")?,
+            max: duration("max_nanos")?,
+            std_dev: duration("std_dev_nanos")?,
+            outliers: number("outliers")? as usize,
+        })
+    }
+}
+
+impl From<&MemoryStats> for JsonValue {
+    #[allow(clippy::cast_precision_loss)]
+    fn from(value: &MemoryStats) -> Self {
+        let mut map: HashMap<String, JsonValue> = HashMap::new();
+
+        map.insert(
+            "total_bytes".into(),
+            JsonValue::Number(value.total_bytes as f64),
+        );
+        map.insert(
+            "total_blocks".into(),
+            JsonValue::Number(value.total_blocks as f64),
+        );
+        map.insert(
+            "max_bytes".into(),
+            JsonValue::Number(value.max_bytes as f64),
+        );
+
+        JsonValue::Object(map)
+    }
+}
+
+impl TryFrom<&JsonValue> for MemoryStats {
+    type Error = String;
+
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    fn try_from(value: &JsonValue) -> Result<Self, Self::Error> {
+        let json = value
+            .get::<HashMap<String, JsonValue>>()
+            .ok_or("Expected timing memory to be a JSON object.")?;
+
+        let number = |key: &str| {
+            json.get(key)
+                .and_then(|v| v.get::<f64>().copied())
+                .map(|x| x as u64)
+                .ok_or(format!("Expected timing memory.{key} to be a number."))
+        };
+
+        Ok(MemoryStats {
+            total_bytes: number("total_bytes")?,
+            total_blocks: number("total_blocks")?,
+            max_bytes: number("max_bytes")?,
+        })
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
 mod tests {
-    use crate::day;
+    use crate::puzzle;
 
     use super::{Timing, Timings};
 
@@ -194,21 +582,42 @@ mod tests {
         Timings {
             data: vec![
                 Timing {
-                    day: day!(1),
+                    id: puzzle!(2023, 1),
+                    parse: None,
                     part_1: Some("10ms".into()),
                     part_2: Some("20ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 3e+10,
                 },
                 Timing {
-                    day: day!(2),
+                    id: puzzle!(2023, 2),
+                    parse: None,
                     part_1: Some("30ms".into()),
                     part_2: Some("40ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 7e+10,
                 },
                 Timing {
-                    day: day!(4),
+                    id: puzzle!(2023, 4),
+                    parse: None,
                     part_1: Some("40ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 4e+10,
                 },
             ],
@@ -216,7 +625,7 @@ mod tests {
     }
 
     mod deserialization {
-        use crate::{day, template::timings::Timings};
+        use crate::{puzzle, template::timings::Timings};
 
         #[test]
         fn handles_json_timings() {
@@ -224,12 +633,45 @@ mod tests {
             let timings = Timings::try_from(json).unwrap();
             assert_eq!(timings.data.len(), 1);
             let timing = timings.data.first().unwrap();
-            assert_eq!(timing.day, day!(1));
+            assert_eq!(timing.id, puzzle!(2023, 1));
             assert_eq!(timing.part_1, Some("1ms".to_string()));
             assert_eq!(timing.part_2, None);
             assert_eq!(timing.total_nanos, 1_000_000_000_f64);
         }
 
+        #[test]
+        fn handles_json_timing_stats() {
+            let json = r#"{ "data": [{ "year": 2022, "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_stats": { "samples": 10, "mean_nanos": 1000000, "min_nanos": 900000, "median_nanos": 950000, "p95_nanos": 1200000, "max_nanos": 1500000, "std_dev_nanos": 20000, "outliers": 1 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            assert_eq!(timing.id, puzzle!(2022, 1));
+            let stats = timing.part_1_stats.as_ref().unwrap();
+            assert_eq!(stats.samples, 10);
+            assert_eq!(stats.median.as_nanos(), 950_000);
+            assert_eq!(stats.outliers, 1);
+            assert_eq!(timing.part_2_stats, None);
+        }
+
+        #[test]
+        fn handles_json_timing_memory() {
+            let json = r#"{ "data": [{ "year": 2023, "day": "01", "part_1": "1ms", "part_2": null, "total_nanos": 1000000, "part_1_memory": { "total_bytes": 4096, "total_blocks": 12, "max_bytes": 1024 } }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            let timing = timings.data.first().unwrap();
+            let memory = timing.part_1_memory.as_ref().unwrap();
+            assert_eq!(memory.total_bytes, 4096);
+            assert_eq!(memory.total_blocks, 12);
+            assert_eq!(memory.max_bytes, 1024);
+            assert_eq!(timing.part_2_memory, None);
+        }
+
+        #[test]
+        fn handles_json_stale_parts() {
+            let json = r#"{ "data": [{ "year": 2023, "day": "01", "part_1": "1ms", "part_2": "2ms", "total_nanos": 1000000, "stale_parts": [2] }] }"#.to_string();
+            let timings = Timings::try_from(json).unwrap();
+            assert_eq!(timings.data[0].stale_parts, vec![2]);
+            assert!(!timings.is_day_complete(puzzle!(2023, 1)));
+        }
+
         #[test]
         fn handles_empty_timings() {
             let json = r#"{ "data": [] }"#.to_string();
@@ -260,7 +702,7 @@ mod tests {
         #[test]
         fn serializes_timings() {
             let timings = get_mock_timings();
-            let value = JsonValue::try_from(timings).unwrap();
+            let value = JsonValue::from(timings);
             assert_eq!(
                 value
                     .get::<HashMap<String, JsonValue>>()
@@ -277,7 +719,7 @@ mod tests {
 
     mod is_day_complete {
         use crate::{
-            day,
+            puzzle,
             template::timings::{Timing, Timings},
         };
 
@@ -285,49 +727,182 @@ mod tests {
         fn handles_completed_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    id: puzzle!(2023, 1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: Some("2ms".into()),
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 3_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), true);
+            assert!(timings.is_day_complete(puzzle!(2023, 1)));
         }
 
         #[test]
         fn handles_partial_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    id: puzzle!(2023, 1),
+                    parse: None,
                     part_1: Some("1ms".into()),
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 1_000_000_000_f64,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(puzzle!(2023, 1)));
         }
 
         #[test]
         fn handles_uncompleted_days() {
             let timings = Timings {
                 data: vec![Timing {
-                    day: day!(1),
+                    id: puzzle!(2023, 1),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 0.0,
                 }],
             };
 
-            assert_eq!(timings.is_day_complete(&day!(1)), false);
+            assert!(!timings.is_day_complete(puzzle!(2023, 1)));
+        }
+    }
+
+    mod from_results {
+        use std::time::Duration;
+
+        use crate::{
+            puzzle,
+            template::{
+                runner::{DayResult, PartResult, PartStatus},
+                stats::{BenchStats, MemoryStats},
+                timings::{Timing, Timings},
+            },
+        };
+
+        fn stats(mean_micros: u64) -> BenchStats {
+            let mean = Duration::from_micros(mean_micros);
+            BenchStats {
+                samples: 10,
+                mean,
+                min: mean,
+                median: mean,
+                p95: mean,
+                max: mean,
+                std_dev: Duration::ZERO,
+                outliers: 0,
+            }
+        }
+
+        #[test]
+        fn converts_day_results() {
+            let result = DayResult {
+                parse: Some(stats(500)),
+                parts: vec![
+                    PartResult {
+                        part: 1,
+                        answer: Some("42".into()),
+                        stats: stats(1000),
+                        memory: Some(MemoryStats {
+                            total_bytes: 2048,
+                            total_blocks: 3,
+                            max_bytes: 1024,
+                        }),
+                        status: PartStatus::Solved,
+                    },
+                    PartResult {
+                        part: 2,
+                        answer: Some("7".into()),
+                        stats: stats(2500),
+                        memory: None,
+                        status: PartStatus::Solved,
+                    },
+                ],
+                ..DayResult::new(puzzle!(2023, 3))
+            };
+
+            let timing = Timing::from(&result);
+            assert_eq!(timing.id, puzzle!(2023, 3));
+            assert_eq!(timing.parse, Some("500.0µs".into()));
+            assert_eq!(timing.part_1, Some("1.0ms".into()));
+            assert_eq!(timing.part_2, Some("2.5ms".into()));
+            assert_eq!(timing.part_2_stats, Some(stats(2500)));
+            assert_eq!(timing.part_1_memory.map(|m| m.max_bytes), Some(1024));
+            assert_eq!(timing.part_2_memory, None);
+            assert_eq!(timing.total_nanos, 4_000_000_f64);
+        }
+
+        #[test]
+        fn excludes_unsolved_parts() {
+            let result = DayResult {
+                parts: vec![
+                    PartResult {
+                        part: 1,
+                        answer: None,
+                        stats: stats(1000),
+                        memory: None,
+                        status: PartStatus::Panicked("oops".into()),
+                    },
+                    PartResult {
+                        part: 2,
+                        answer: None,
+                        stats: stats(2500),
+                        memory: None,
+                        status: PartStatus::NoResult,
+                    },
+                ],
+                ..DayResult::new(puzzle!(2023, 3))
+            };
+
+            let timing = Timing::from(&result);
+            assert_eq!(timing.part_1, None);
+            assert_eq!(timing.part_2, None);
+            assert_eq!(timing.total_nanos, 0_f64);
+        }
+
+        #[test]
+        fn skips_failed_days() {
+            let results = [
+                DayResult::new(puzzle!(2023, 1)),
+                DayResult {
+                    error: Some("missing input".into()),
+                    ..DayResult::new(puzzle!(2023, 2))
+                },
+            ];
+
+            let timings = Timings::from(results.as_slice());
+            assert_eq!(timings.data.len(), 1);
+            assert_eq!(timings.data[0].id, puzzle!(2023, 1));
+            assert_eq!(timings.data[0].part_1, None);
         }
     }
 
     mod merge {
         use crate::{
-            day,
-            template::timings::{Timing, Timings},
+            puzzle,
+            template::{
+                stats::MemoryStats,
+                timings::{Timing, Timings},
+            },
         };
 
         use super::get_mock_timings;
@@ -337,18 +912,25 @@ mod tests {
             let timings = get_mock_timings();
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(3),
+                    id: puzzle!(2023, 3),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 4);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
-            assert_eq!(merged.data[2].day, day!(3));
-            assert_eq!(merged.data[3].day, day!(4));
+            assert_eq!(merged.data[0].id, puzzle!(2023, 1));
+            assert_eq!(merged.data[1].id, puzzle!(2023, 2));
+            assert_eq!(merged.data[2].id, puzzle!(2023, 3));
+            assert_eq!(merged.data[3].id, puzzle!(2023, 4));
         }
 
         #[test]
@@ -357,19 +939,71 @@ mod tests {
 
             let other = Timings {
                 data: vec![Timing {
-                    day: day!(2),
+                    id: puzzle!(2023, 2),
+                    parse: None,
                     part_1: None,
                     part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
                     total_nanos: 0_f64,
                 }],
             };
             let merged = timings.merge(&other);
 
             assert_eq!(merged.data.len(), 3);
-            assert_eq!(merged.data[0].day, day!(1));
-            assert_eq!(merged.data[1].day, day!(2));
+            assert_eq!(merged.data[0].id, puzzle!(2023, 1));
+            assert_eq!(merged.data[1].id, puzzle!(2023, 2));
+            assert_eq!(merged.data[1].part_1, Some("30ms".into()));
+            assert_eq!(merged.data[1].part_2, Some("40ms".into()));
+            assert_eq!(merged.data[1].stale_parts, vec![1, 2]);
             assert_eq!(merged.data[1].total_nanos, 0_f64);
-            assert_eq!(merged.data[2].day, day!(4));
+            assert_eq!(merged.data[2].id, puzzle!(2023, 4));
+        }
+
+        #[test]
+        fn keeps_stored_timings_of_unsolved_parts_as_stale() {
+            let timings = get_mock_timings();
+            assert!(timings.is_day_complete(puzzle!(2023, 2)));
+
+            let other = Timings {
+                data: vec![Timing {
+                    id: puzzle!(2023, 2),
+                    parse: None,
+                    part_1: Some("5ms".into()),
+                    part_2: None,
+                    parse_stats: None,
+                    part_1_stats: None,
+                    part_2_stats: None,
+                    part_1_memory: None,
+                    part_2_memory: None,
+                    stale_parts: vec![],
+                    total_nanos: 5e+6,
+                }],
+            };
+            let merged = timings.merge(&other);
+
+            assert_eq!(merged.data[1].part_1, Some("5ms".into()));
+            assert_eq!(merged.data[1].part_2, Some("40ms".into()));
+            assert_eq!(merged.data[1].stale_parts, vec![2]);
+            assert_eq!(merged.data[1].total_nanos, 5e+6);
+            assert_eq!(merged.total_millis(), 70_005.0);
+            assert!(!merged.is_day_complete(puzzle!(2023, 2)));
+
+            let resolved = merged.merge(&Timings {
+                data: vec![Timing {
+                    part_2: Some("6ms".into()),
+                    total_nanos: 1.1e+7,
+                    ..other.data[0].clone()
+                }],
+            });
+
+            assert!(resolved.data[1].stale_parts.is_empty());
+            assert_eq!(resolved.data[1].total_nanos, 1.1e+7);
+            assert!(resolved.is_day_complete(puzzle!(2023, 2)));
         }
 
         #[test]
@@ -387,5 +1021,41 @@ mod tests {
             let merged = timings.merge(&other);
             assert_eq!(merged.data.len(), 3);
         }
+
+        #[test]
+        fn keeps_memory_of_stored_timings() {
+            let mut timings = get_mock_timings();
+            timings.data[1].part_1_memory = Some(memory(1024));
+
+            let merged = timings.merge(&get_mock_timings());
+            assert_eq!(merged.data[1].part_1_memory, Some(memory(1024)));
+        }
+
+        #[test]
+        fn merges_memory() {
+            let timings = get_mock_timings();
+
+            let mut other = get_mock_timings();
+            other.data[0].part_2_memory = Some(memory(2048));
+            other.data[0].total_nanos = 0_f64;
+            other.data[1].id = puzzle!(2023, 3);
+            other.data[1].part_1_memory = Some(memory(512));
+
+            let merged = timings.merge_memory(&other);
+            assert_eq!(merged.data.len(), 4);
+            assert_eq!(merged.data[0].part_2_memory, Some(memory(2048)));
+            assert_eq!(merged.data[0].total_nanos, 3e+10);
+            assert_eq!(merged.data[2].id, puzzle!(2023, 3));
+            assert_eq!(merged.data[2].part_1, None);
+            assert_eq!(merged.data[2].part_1_memory, Some(memory(512)));
+        }
+
+        fn memory(max_bytes: u64) -> MemoryStats {
+            MemoryStats {
+                total_bytes: max_bytes * 2,
+                total_blocks: 1,
+                max_bytes,
+            }
+        }
     }
 }
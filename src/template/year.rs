@@ -0,0 +1,282 @@
+use std::collections::HashMap;
+use std::env;
+use std::error::Error;
+use std::fmt::Display;
+use std::str::FromStr;
+
+use tinyjson::JsonValue;
+
+#[cfg(feature = "today")]
+use chrono::{Datelike, FixedOffset, Utc};
+
+#[cfg(feature = "today")]
+use crate::template::day::SERVER_UTC_OFFSET;
+use crate::template::{all_days, config, Day};
+
+/// A year of advent (i.e. an integer from 2015 onwards).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Year(u16);
+
+impl Year {
+    /// Creates a [`Year`] from the provided value if it's a year of advent,
+    /// returns [`None`] otherwise.
+    pub fn new(year: u16) -> Option<Self> {
+        if year < 2015 {
+            return None;
+        }
+        Some(Self(year))
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __new_unchecked(year: u16) -> Self {
+        Self(year)
+    }
+
+    // Not part of the public API
+    #[doc(hidden)]
+    pub const fn __from_env_unchecked(year: Option<&str>) -> Self {
+        let Some(year) = year else {
+            panic!("`AOC_YEAR` is not set and `aoc.toml` has no `year`, pass the year to `solution!` instead, e.g. `solution!(year: 2023, 1)`");
+        };
+
+        let bytes = year.as_bytes();
+        let mut value: u16 = 0;
+        let mut i = 0;
+
+        while i < bytes.len() {
+            assert!(bytes[i].is_ascii_digit(), "`AOC_YEAR` is not a number");
+            value = value * 10 + (bytes[i] - b'0') as u16;
+            i += 1;
+        }
+
+        assert!(value >= 2015, "`AOC_YEAR` is not a year of advent");
+        Self(value)
+    }
+
+    /// Reads the year from the `AOC_YEAR` environment variable,
+    /// falling back to its value at compile time.
+    pub fn from_env() -> Option<Self> {
+        env::var("AOC_YEAR")
+            .ok()
+            .or(option_env!("AOC_YEAR").map(String::from))?
+            .parse()
+            .ok()
+    }
+
+    /// The year of commands that are not passed one: `AOC_YEAR` if set, otherwise the `year`
+    /// in `aoc.toml`.
+    pub fn configured() -> Option<Self> {
+        Self::from_env().or(config::get().year)
+    }
+
+    /// Converts the [`Year`] into an [`u16`].
+    pub fn into_inner(self) -> u16 {
+        self.0
+    }
+}
+
+#[cfg(feature = "today")]
+impl Year {
+    /// Returns the current year if it's december, `None` otherwise.
+    pub fn today() -> Option<Self> {
+        let offset = FixedOffset::east_opt(SERVER_UTC_OFFSET * 3600)?;
+        let today = Utc::now().with_timezone(&offset);
+        if today.month() == 12 {
+            Self::new(u16::try_from(today.year()).ok()?)
+        } else {
+            None
+        }
+    }
+}
+
+impl Display for Year {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", self.0)
+    }
+}
+
+impl FromStr for Year {
+    type Err = YearFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let year = s.parse().map_err(|_| YearFromStrError)?;
+        Self::new(year).ok_or(YearFromStrError)
+    }
+}
+
+/// An error which can be returned when parsing a [`Year`].
+#[derive(Debug)]
+pub struct YearFromStrError;
+
+impl Error for YearFromStrError {}
+
+impl Display for YearFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a year of advent, starting with 2015")
+    }
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Identifies a single puzzle by its year and day.
+///
+/// # Display
+/// This value displays as `<year>-<day>`, which is also the name of the solution binary.
+///
+/// ```
+/// # use advent_of_code::template::{Day, PuzzleId, Year};
+/// let id = PuzzleId::new(Year::new(2023).unwrap(), Day::new(8).unwrap());
+/// assert_eq!(id.to_string(), "2023-08")
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct PuzzleId {
+    pub year: Year,
+    pub day: Day,
+}
+
+impl PuzzleId {
+    pub const fn new(year: Year, day: Day) -> Self {
+        Self { year, day }
+    }
+
+    /// Pairs a day with the year passed via `--year`, falling back to the configured year.
+    pub fn with_year_or_configured(year: Option<Year>, day: Day) -> Result<Self, String> {
+        let year = year.or_else(Year::configured).ok_or(
+            "no year specified. Pass `--year <year>`, set `AOC_YEAR` or set `year` in `aoc.toml`.",
+        )?;
+        Ok(Self::new(year, day))
+    }
+}
+
+impl PuzzleId {
+    /// Writes the `year` and `day` keys of a JSON object.
+    pub(crate) fn write_json(self, map: &mut HashMap<String, JsonValue>) {
+        map.insert(
+            "year".into(),
+            JsonValue::Number(f64::from(self.year.into_inner())),
+        );
+        map.insert("day".into(), JsonValue::String(self.day.to_string()));
+    }
+
+    /// Reads the `year` and `day` keys of a JSON object. Data stored before years were
+    /// tracked does not have a `year` key and is attributed to the configured year.
+    #[allow(clippy::cast_possible_truncation, clippy::cast_sign_loss)]
+    pub(crate) fn read_json(json: &HashMap<String, JsonValue>, name: &str) -> Result<Self, String> {
+        let day = json
+            .get("day")
+            .and_then(|v| v.get::<String>())
+            .and_then(|day| Day::from_str(day).ok())
+            .ok_or(format!("Expected {name}.day to be a Day struct."))?;
+
+        let year = match json.get("year") {
+            Some(v) => v.get::<f64>().and_then(|year| Year::new(*year as u16)),
+            None => Year::configured(),
+        }
+        .ok_or(format!("Expected {name}.year to be a year of advent."))?;
+
+        Ok(Self { year, day })
+    }
+}
+
+impl Display for PuzzleId {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}-{}", self.year, self.day)
+    }
+}
+
+impl FromStr for PuzzleId {
+    type Err = PuzzleIdFromStrError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (year, day) = s.split_once('-').ok_or(PuzzleIdFromStrError)?;
+        Ok(Self {
+            year: year.parse().map_err(|_| PuzzleIdFromStrError)?,
+            day: day.parse().map_err(|_| PuzzleIdFromStrError)?,
+        })
+    }
+}
+
+/// An error which can be returned when parsing a [`PuzzleId`].
+#[derive(Debug)]
+pub struct PuzzleIdFromStrError;
+
+impl Error for PuzzleIdFromStrError {}
+
+impl Display for PuzzleIdFromStrError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str("expecting a puzzle in the format `<year>-<day>`, e.g. `2023-08`")
+    }
+}
+
+/// An iterator that yields every puzzle of a year.
+pub fn all_puzzles(year: Year) -> impl Iterator<Item = PuzzleId> {
+    all_days().map(move |day| PuzzleId::new(year, day))
+}
+
+/* -------------------------------------------------------------------------- */
+
+/// Creates a [`Year`] value in a const context.
+#[macro_export]
+macro_rules! year {
+    ($year:expr) => {{
+        const _ASSERT: () = assert!(
+            $year >= 2015,
+            concat!("invalid year `", $year, "`, expecting 2015 or later"),
+        );
+        $crate::template::Year::__new_unchecked($year)
+    }};
+}
+
+/// Creates a [`PuzzleId`] value in a const context.
+#[macro_export]
+macro_rules! puzzle {
+    ($year:expr, $day:expr) => {
+        $crate::template::PuzzleId::new($crate::year!($year), $crate::day!($day))
+    };
+}
+
+/* -------------------------------------------------------------------------- */
+
+#[cfg(all(test, feature = "test_lib"))]
+mod tests {
+    use super::{all_puzzles, PuzzleId, Year};
+    use std::collections::HashMap;
+    use std::str::FromStr;
+    use tinyjson::JsonValue;
+
+    #[test]
+    fn parses_years() {
+        assert_eq!(Year::from_str("2023").unwrap(), year!(2023));
+        assert!(Year::from_str("2014").is_err());
+        assert!(Year::from_str("23x").is_err());
+        assert_eq!(Year::__from_env_unchecked(Some("2016")), year!(2016));
+    }
+
+    #[test]
+    fn parses_puzzle_ids() {
+        let id = PuzzleId::from_str("2023-08").unwrap();
+        assert_eq!(id, puzzle!(2023, 8));
+        assert_eq!(id.to_string(), "2023-08");
+        assert!(PuzzleId::from_str("2023").is_err());
+        assert!(PuzzleId::from_str("2023-26").is_err());
+    }
+
+    #[test]
+    fn orders_puzzle_ids() {
+        let mut ids = [puzzle!(2023, 1), puzzle!(2022, 25)];
+        ids.sort();
+        assert_eq!(ids[0].year, year!(2022));
+        assert_eq!(all_puzzles(year!(2023)).count(), 25);
+    }
+
+    #[test]
+    fn reads_puzzle_ids_from_json() {
+        let mut map = HashMap::new();
+        puzzle!(2022, 3).write_json(&mut map);
+        assert_eq!(PuzzleId::read_json(&map, "x"), Ok(puzzle!(2022, 3)));
+
+        map.insert("year".into(), JsonValue::Number(2014.0));
+        assert!(PuzzleId::read_json(&map, "x").is_err());
+    }
+}